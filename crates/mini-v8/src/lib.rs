@@ -5,6 +5,7 @@ mod conversion;
 mod error;
 mod function;
 mod mini_v8;
+mod module;
 mod object;
 mod string;
 #[cfg(test)] mod tests;
@@ -14,6 +15,7 @@ pub use crate::array::*;
 pub use crate::error::*;
 pub use crate::function::*;
 pub use crate::mini_v8::*;
+pub use crate::module::*;
 pub use crate::object::*;
 pub use crate::string::*;
 pub use crate::value::*;
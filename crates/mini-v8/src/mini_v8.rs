@@ -18,6 +18,7 @@ impl MiniV8 {
         initialize_v8();
         let mut isolate = v8::Isolate::new(Default::default());
         initialize_slots(&mut isolate);
+        isolate.set_promise_reject_callback(promise_reject_callback);
         MiniV8 {
             interface: Interface::new(isolate),
         }
@@ -287,6 +288,23 @@ impl MiniV8 {
         });
     }
 
+    /// Returns the reasons of the promises that were rejected without a handler since the last
+    /// call, in the order they were rejected. Promises that got a handler in the meantime are left
+    /// out, so this is best called after the microtask queue has been processed.
+    pub fn take_unhandled_rejections(&self) -> Vec<Value> {
+        let rejections = self.interface.use_slot(|r: &Rejections| r.0.clone());
+        let rejections = std::mem::take(&mut *rejections.borrow_mut());
+        self.scope(|scope| {
+            rejections
+                .into_iter()
+                .map(|(_, reason)| {
+                    let reason = v8::Local::new(scope, reason);
+                    Value::from_v8_value(self, scope, reason)
+                })
+                .collect()
+        })
+    }
+
     // Opens a new handle scope in the global context. Nesting calls to this or `MiniV8::try_catch`
     // will cause a panic (unless a callback is entered, see `MiniV8::create_function`).
     pub(crate) fn scope<F, T>(&self, func: F) -> T
@@ -305,6 +323,11 @@ impl MiniV8 {
         self.interface.try_catch(func)
     }
 
+    // The modules compiled so far and the loader for new ones.
+    pub(crate) fn module_registry(&self) -> Rc<RefCell<ModuleRegistry>> {
+        self.interface.use_slot(|m: &Modules| m.0.clone())
+    }
+
     pub(crate) fn exception(&self, scope: &mut v8::TryCatch<v8::HandleScope>) -> Result<()> {
         if scope.has_terminated() {
             Err(Error::Timeout)
@@ -449,9 +472,34 @@ fn initialize_slots(isolate: &mut v8::Isolate) {
         context: global_context,
     });
     scope.set_slot(AnyMap(Rc::new(RefCell::new(BTreeMap::new()))));
+    scope.set_slot(Modules(Rc::new(RefCell::new(ModuleRegistry::default()))));
+    scope.set_slot(Rejections(Rc::new(RefCell::new(Vec::new()))));
 }
 
-fn create_string<'s>(scope: &mut v8::HandleScope<'s>, value: &str) -> v8::Local<'s, v8::String> {
+// Tracks the promises rejected without a handler, until they get one.
+extern "C" fn promise_reject_callback(message: v8::PromiseRejectMessage) {
+    let scope = &mut unsafe { v8::CallbackScope::new(&message) };
+    let rejections = scope.get_slot::<Rejections>().unwrap().0.clone();
+    let promise = message.get_promise();
+    match message.get_event() {
+        v8::PromiseRejectEvent::PromiseRejectWithNoHandler => {
+            let reason = message
+                .get_value()
+                .unwrap_or_else(|| v8::undefined(scope).into());
+            let promise = v8::Global::new(scope, promise);
+            let reason = v8::Global::new(scope, reason);
+            rejections.borrow_mut().push((promise, reason));
+        }
+        v8::PromiseRejectEvent::PromiseHandlerAddedAfterReject => {
+            rejections
+                .borrow_mut()
+                .retain(|(rejected, _)| v8::Local::new(scope, rejected) != promise);
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn create_string<'s>(scope: &mut v8::HandleScope<'s>, value: &str) -> v8::Local<'s, v8::String> {
     v8::String::new(scope, value).expect("string exceeds maximum length")
 }
 
@@ -485,6 +533,8 @@ struct CallbackInfo {
 
 struct AnyMap(Rc<RefCell<BTreeMap<StdString, Box<dyn Any>>>>);
 
+struct Rejections(Rc<RefCell<Vec<(v8::Global<v8::Promise>, v8::Global<v8::Value>)>>>);
+
 // A JavaScript script.
 #[derive(Clone, Debug, Default)]
 pub struct Script {
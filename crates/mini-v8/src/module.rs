@@ -0,0 +1,186 @@
+use crate::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::string::String as StdString;
+
+/// Finds and loads the ES modules imported through `MiniV8::import_module`.
+pub trait ModuleLoader {
+    /// Resolves the `specifier` of an import found in the module named `referrer` to the name of
+    /// the imported module. `referrer` is empty for the module passed to `MiniV8::import_module`.
+    ///
+    /// Modules are compiled and evaluated once per name, so names should be canonical (e.g.
+    /// normalized absolute paths).
+    fn resolve(&self, specifier: &str, referrer: &str) -> Result<StdString>;
+
+    /// Returns the source of the module with the given resolved name.
+    fn load(&self, name: &str) -> Result<StdString>;
+}
+
+pub(crate) struct Modules(pub(crate) Rc<RefCell<ModuleRegistry>>);
+
+#[derive(Default)]
+pub(crate) struct ModuleRegistry {
+    loader: Option<Rc<dyn ModuleLoader>>,
+    modules: Vec<(StdString, v8::Global<v8::Module>)>,
+}
+
+impl ModuleRegistry {
+    fn get(&self, name: &str) -> Option<&v8::Global<v8::Module>> {
+        self.modules
+            .iter()
+            .find(|(module_name, _)| module_name == name)
+            .map(|(_, module)| module)
+    }
+}
+
+impl MiniV8 {
+    /// Sets the loader used to resolve and load ES modules, replacing any previous loader. Modules
+    /// that were already imported stay cached by their names.
+    pub fn set_module_loader<L: ModuleLoader + 'static>(&self, loader: L) {
+        self.module_registry().borrow_mut().loader = Some(Rc::new(loader));
+    }
+
+    /// Imports an ES module along with its static imports, evaluates it and returns its namespace
+    /// object (the module's exports).
+    ///
+    /// A module is only evaluated the first time it is imported. Top-level `await` is supported as
+    /// far as the microtask queue can settle it: a module still awaiting something else (e.g. a
+    /// timer) returns its namespace before it has finished evaluating.
+    pub fn import_module(&self, specifier: &str) -> Result<Object> {
+        let registry = self.module_registry();
+        let loader = registry
+            .borrow()
+            .loader
+            .clone()
+            .ok_or_else(|| Error::ExternalError("no module loader has been set".into()))?;
+        let name = loader.resolve(specifier, "")?;
+
+        let namespace = self.try_catch(|scope| {
+            let module = self.compile_module(scope, &registry, &*loader, &name)?;
+            if module.get_status() == v8::ModuleStatus::Uninstantiated {
+                module.instantiate_module(scope, resolve_module);
+                self.exception(scope)?;
+            }
+            if module.get_status() == v8::ModuleStatus::Instantiated {
+                let result = module.evaluate(scope);
+                self.exception(scope)?;
+                // Modules evaluate to a promise, settled once top-level `await`s are done
+                if let Some(Ok(promise)) = result.map(v8::Local::<v8::Promise>::try_from) {
+                    scope.perform_microtask_checkpoint();
+                    if promise.state() == v8::PromiseState::Rejected {
+                        let exception = promise.result(scope);
+                        return Err(Error::Value(Value::from_v8_value(self, scope, exception)));
+                    }
+                }
+            }
+            if module.get_status() == v8::ModuleStatus::Errored {
+                let exception = module.get_exception();
+                return Err(Error::Value(Value::from_v8_value(self, scope, exception)));
+            }
+            let namespace = module.get_module_namespace();
+            Ok(Value::from_v8_value(self, scope, namespace))
+        })?;
+        namespace.into(self)
+    }
+
+    // Compiles the module and, depth-first, the modules it imports, so that they can be linked
+    // without calling back into the loader.
+    fn compile_module<'s>(
+        &self,
+        scope: &mut v8::TryCatch<v8::HandleScope<'s>>,
+        registry: &Rc<RefCell<ModuleRegistry>>,
+        loader: &dyn ModuleLoader,
+        name: &str,
+    ) -> Result<v8::Local<'s, v8::Module>> {
+        if let Some(module) = registry.borrow().get(name) {
+            return Ok(v8::Local::new(scope, module));
+        }
+
+        let source = loader.load(name)?;
+        let source = create_string(scope, &source);
+        let resource_name = create_string(scope, name).into();
+        let source_map_url = create_string(scope, "").into();
+        let origin = v8::ScriptOrigin::new(
+            scope,
+            resource_name,
+            0,
+            0,
+            false,
+            0,
+            source_map_url,
+            false,
+            false,
+            true,
+        );
+        let source = v8::script_compiler::Source::new(source, Some(&origin));
+        let module = v8::script_compiler::compile_module(scope, source);
+        self.exception(scope)?;
+        let module = module.unwrap();
+        // Registered before its imports so that a cycle finds it instead of compiling it again
+        let registered = registry.borrow().modules.len();
+        registry
+            .borrow_mut()
+            .modules
+            .push((name.to_owned(), v8::Global::new(scope, module)));
+
+        let requests = module.get_module_requests();
+        for i in 0..requests.length() {
+            let request = requests.get(scope, i).unwrap();
+            let request = v8::Local::<v8::ModuleRequest>::try_from(request).unwrap();
+            let specifier = request.get_specifier().to_rust_string_lossy(scope);
+            let compiled = loader
+                .resolve(&specifier, name)
+                .and_then(|dependency| self.compile_module(scope, registry, loader, &dependency));
+            if let Err(error) = compiled {
+                // Drops the module and the imports compiled for it, a later import of the module
+                // loads it again instead of linking a module that can't be instantiated
+                registry.borrow_mut().modules.truncate(registered);
+                return Err(error);
+            }
+        }
+        Ok(module)
+    }
+}
+
+// Links an import to a module compiled by `MiniV8::compile_module`.
+fn resolve_module<'a>(
+    context: v8::Local<'a, v8::Context>,
+    specifier: v8::Local<'a, v8::String>,
+    _import_assertions: v8::Local<'a, v8::FixedArray>,
+    referrer: v8::Local<'a, v8::Module>,
+) -> Option<v8::Local<'a, v8::Module>> {
+    let scope = &mut unsafe { v8::CallbackScope::new(context) };
+    let registry = scope.get_slot::<Modules>().unwrap().0.clone();
+    let registry = registry.borrow();
+    let specifier = specifier.to_rust_string_lossy(scope);
+
+    let referrer_name = registry
+        .modules
+        .iter()
+        .find(|(_, module)| v8::Local::new(scope, module) == referrer)
+        .map(|(name, _)| name.clone());
+    let resolved = match (referrer_name, &registry.loader) {
+        (Some(referrer), Some(loader)) => loader
+            .resolve(&specifier, &referrer)
+            .map_err(|e| e.to_string())
+            .and_then(|name| {
+                registry
+                    .get(&name)
+                    .ok_or_else(|| format!("module {} has not been loaded", name))
+            }),
+        _ => Err(format!(
+            "cannot resolve {} from an unknown module",
+            specifier
+        )),
+    };
+
+    match resolved {
+        Ok(module) => Some(v8::Local::new(scope, module)),
+        Err(message) => {
+            let message = create_string(scope, &message);
+            let exception = v8::Exception::error(scope, message);
+            scope.throw_exception(exception);
+            None
+        }
+    }
+}
@@ -133,3 +133,22 @@ fn make_test_user_data() -> (Rc<RefCell<usize>>, TestUserData) {
     let count = Rc::new(RefCell::new(0));
     (count.clone(), TestUserData { count })
 }
+
+#[test]
+fn unhandled_rejections() {
+    let mv8 = MiniV8::new();
+    mv8.eval::<_, Value>(r#"
+        Promise.reject(1);
+        Promise.reject(2).catch(() => {});
+        const late = Promise.reject(3);
+        (async () => { throw 4; })();
+        late.then(null, () => {});
+    "#).unwrap();
+    mv8.run_microtasks();
+    let reasons: Vec<f64> = mv8.take_unhandled_rejections()
+        .iter()
+        .map(|reason| reason.as_number().unwrap())
+        .collect();
+    assert_eq!(vec![1.0, 4.0], reasons);
+    assert!(mv8.take_unhandled_rejections().is_empty());
+}
@@ -2,6 +2,7 @@ mod array;
 mod conversion;
 mod function;
 mod mini_v8;
+mod module;
 mod object;
 mod string;
 mod value;
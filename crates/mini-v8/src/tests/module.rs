@@ -0,0 +1,85 @@
+use crate::*;
+use std::collections::HashMap;
+use std::string::String as StdString;
+
+// Modules named by their specifiers, `./` is stripped so relative imports resolve to the same name.
+struct MemoryLoader(HashMap<&'static str, &'static str>);
+
+impl ModuleLoader for MemoryLoader {
+    fn resolve(&self, specifier: &str, _referrer: &str) -> Result<StdString> {
+        Ok(specifier.trim_start_matches("./").to_owned())
+    }
+
+    fn load(&self, name: &str) -> Result<StdString> {
+        self.0
+            .get(name)
+            .map(|source| source.to_string())
+            .ok_or_else(|| Error::ExternalError(format!("no module named {}", name).into()))
+    }
+}
+
+fn mv8_with_modules(modules: &[(&'static str, &'static str)]) -> MiniV8 {
+    let mv8 = MiniV8::new();
+    mv8.set_module_loader(MemoryLoader(modules.iter().cloned().collect()));
+    mv8
+}
+
+#[test]
+fn import_module_exports() {
+    let mv8 = mv8_with_modules(&[
+        ("math", "export const add = (a, b) => a + b; export default 'math';"),
+        ("main", "import name, { add } from './math'; export const sum = add(2, 3); export { name };"),
+    ]);
+    let exports = mv8.import_module("main").unwrap();
+    assert_eq!(5, exports.get::<_, usize>("sum").unwrap());
+    assert_eq!("math", exports.get::<_, StdString>("name").unwrap());
+}
+
+#[test]
+fn import_module_evaluates_once() {
+    let mv8 = mv8_with_modules(&[
+        ("counter", "globalThis.count = (globalThis.count || 0) + 1;"),
+        ("a", "import './counter';"),
+        ("b", "import './counter'; import './a';"),
+    ]);
+    mv8.import_module("a").unwrap();
+    mv8.import_module("b").unwrap();
+    mv8.import_module("b").unwrap();
+    assert_eq!(1, mv8.eval::<_, usize>("count").unwrap());
+}
+
+#[test]
+fn import_module_top_level_await() {
+    let mv8 = mv8_with_modules(&[("main", "export const value = await Promise.resolve(42);")]);
+    let exports = mv8.import_module("main").unwrap();
+    assert_eq!(42, exports.get::<_, usize>("value").unwrap());
+}
+
+#[test]
+fn import_module_errors() {
+    let mv8 = mv8_with_modules(&[
+        ("missing", "import './nowhere';"),
+        ("syntax", "export const = 1;"),
+        ("throws", "throw new Error('boom');"),
+    ]);
+    match mv8.import_module("missing") {
+        Err(Error::ExternalError(e)) => assert_eq!("no module named nowhere", e.to_string()),
+        result => panic!("unexpected result: {:?}", result),
+    }
+    match mv8.import_module("syntax") {
+        Err(Error::Value(Value::Object(_))) => {},
+        result => panic!("unexpected result: {:?}", result),
+    }
+    match mv8.import_module("throws") {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!("boom", error.get::<_, StdString>("message").unwrap());
+        },
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn import_module_without_loader() {
+    let mv8 = MiniV8::new();
+    assert!(mv8.import_module("main").is_err());
+}
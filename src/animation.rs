@@ -0,0 +1,89 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use mini_v8::Function;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Enter,
+    Leave,
+}
+
+// A running enter or leave transition of an element, `done` tells Vue's BaseTransition
+// that it finished, for leave transitions that's when the element actually gets removed
+pub struct Animation {
+    phase: Phase,
+    start: Instant,
+    duration: Duration,
+    offset: egui::Vec2,
+    done: Function,
+}
+
+fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+fn ease_out_cubic_inverse(eased: f32) -> f32 {
+    1.0 - (1.0 - eased).cbrt()
+}
+
+impl Animation {
+    // Starts where `previous` currently is, so a leave interrupting an enter doesn't jump
+    pub fn new(
+        phase: Phase,
+        duration: Duration,
+        offset: egui::Vec2,
+        done: Function,
+        previous: Option<&Animation>,
+    ) -> Self {
+        let mut start = Instant::now();
+        if let Some(previous) = previous.filter(|previous| previous.phase != phase) {
+            let visibility = previous.visibility();
+            let eased = match phase {
+                Phase::Enter => visibility,
+                Phase::Leave => 1.0 - visibility,
+            };
+            start -= duration.mul_f32(ease_out_cubic_inverse(eased));
+        }
+        Self {
+            phase,
+            start,
+            duration,
+            offset,
+            done,
+        }
+    }
+
+    fn linear_progress(&self) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        (self.start.elapsed().as_secs_f32() / self.duration.as_secs_f32()).clamp(0.0, 1.0)
+    }
+
+    pub fn finished(&self) -> bool {
+        self.linear_progress() >= 1.0
+    }
+
+    // How much the element is shown, from 0 (gone) to 1 (in place)
+    fn visibility(&self) -> f32 {
+        let progress = ease_out_cubic(self.linear_progress());
+        match self.phase {
+            Phase::Enter => progress,
+            Phase::Leave => 1.0 - progress,
+        }
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.visibility()
+    }
+
+    // Elements slide in from and out to `offset` relative to their place in the layout
+    pub fn translation(&self) -> egui::Vec2 {
+        self.offset * (1.0 - self.visibility())
+    }
+
+    pub fn done(&self) -> &Function {
+        &self.done
+    }
+}
@@ -0,0 +1,170 @@
+use mini_v8::{Function, MiniV8, Object};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// `atob` and `btoa` throw an InvalidCharacterError like browsers do, `vgui.base64` encodes bytes
+// (or the UTF-8 of a string) and decodes into a Uint8Array
+const PRELUDE: &str = r#"
+(native) => {
+    const invalidCharacter = (message) =>
+        Object.assign(new Error(message), { name: 'InvalidCharacterError' });
+
+    globalThis.btoa = (data) => {
+        const encoded = native.encodeLatin1(String(data));
+        if (encoded === null) {
+            throw invalidCharacter('The string to be encoded contains characters outside of the Latin1 range');
+        }
+        return encoded;
+    };
+    globalThis.atob = (data) => {
+        const decoded = native.decodeLatin1(String(data));
+        if (decoded === null) {
+            throw invalidCharacter('The string to be decoded is not correctly encoded');
+        }
+        return decoded;
+    };
+
+    return {
+        encode(data) {
+            if (typeof data === 'string') {
+                return native.encode(Array.from(new TextEncoder().encode(data)));
+            }
+            if (data instanceof ArrayBuffer) {
+                data = new Uint8Array(data);
+            } else if (ArrayBuffer.isView(data)) {
+                data = new Uint8Array(data.buffer, data.byteOffset, data.byteLength);
+            }
+            return native.encode(Array.from(data));
+        },
+        decode(text) {
+            const bytes = native.decode(String(text));
+            if (bytes === null) {
+                throw invalidCharacter('The string to be decoded is not correctly encoded');
+            }
+            return new Uint8Array(bytes);
+        },
+    };
+}
+"#;
+
+pub fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | ((*byte as u32) << (16 - 8 * i))
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((group >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+// Forgiving base64 decoding: ASCII whitespace is skipped and the padding is optional, anything
+// else outside the alphabet is an error
+pub fn decode(text: &str) -> Option<Vec<u8>> {
+    let mut digits: Vec<u8> = text
+        .bytes()
+        .filter(|byte| !matches!(byte, b' ' | b'\t' | b'\n' | b'\x0C' | b'\r'))
+        .collect();
+    if digits.len() % 4 == 0 {
+        for _ in 0..2 {
+            if digits.last() == Some(&b'=') {
+                digits.pop();
+            }
+        }
+    }
+    if digits.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+    for chunk in digits.chunks(4) {
+        let mut group = 0u32;
+        for (i, digit) in chunk.iter().enumerate() {
+            let value = ALPHABET.iter().position(|c| c == digit)? as u32;
+            group |= value << (18 - 6 * i);
+        }
+        bytes.extend((0..chunk.len() - 1).map(|i| (group >> (16 - 8 * i)) as u8));
+    }
+    Some(bytes)
+}
+
+// Sets `atob` and `btoa`, returns the `vgui.base64` object
+pub fn register(isolate: &MiniV8) -> Object {
+    let native = isolate.create_object();
+
+    // encodeLatin1(string), null when a character doesn't fit in a byte
+    let encode_latin1 = isolate.create_function(|invocation| {
+        let (text,): (String,) = invocation.args.into(&invocation.mv8)?;
+        let bytes: Option<Vec<u8>> = text.chars().map(|c| u8::try_from(c).ok()).collect();
+        Ok(bytes.map(|bytes| encode(&bytes)))
+    });
+    native
+        .set("encodeLatin1", encode_latin1)
+        .expect("Failed to set encodeLatin1");
+
+    // decodeLatin1(string), one character per byte
+    let decode_latin1 = isolate.create_function(|invocation| {
+        let (text,): (String,) = invocation.args.into(&invocation.mv8)?;
+        Ok(decode(&text).map(|bytes| bytes.into_iter().map(char::from).collect::<String>()))
+    });
+    native
+        .set("decodeLatin1", decode_latin1)
+        .expect("Failed to set decodeLatin1");
+
+    // encode(bytes) and decode(string), bytes as plain arrays
+    let encode_bytes = isolate.create_function(|invocation| {
+        let (bytes,): (Vec<u8>,) = invocation.args.into(&invocation.mv8)?;
+        Ok(encode(&bytes))
+    });
+    native
+        .set("encode", encode_bytes)
+        .expect("Failed to set encode");
+    let decode_bytes = isolate.create_function(|invocation| {
+        let (text,): (String,) = invocation.args.into(&invocation.mv8)?;
+        Ok(decode(&text))
+    });
+    native
+        .set("decode", decode_bytes)
+        .expect("Failed to set decode");
+
+    isolate
+        .eval::<_, Function>(PRELUDE)
+        .and_then(|prelude| prelude.call::<_, Object>((native,)))
+        .expect("Failed to set atob and btoa")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_with_padding() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg==");
+        assert_eq!(encode(b"fo"), "Zm8=");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(&[0xFF, 0xFE, 0x00, 0x3E]), "//4APg==");
+    }
+
+    #[test]
+    fn decodes_forgivingly() {
+        assert_eq!(decode("Zm9vYg==").unwrap(), b"foob");
+        assert_eq!(decode("Zm9vYg").unwrap(), b"foob");
+        assert_eq!(decode(" Zm9v\nYmE= ").unwrap(), b"fooba");
+        assert_eq!(decode("//4APg==").unwrap(), [0xFF, 0xFE, 0x00, 0x3E]);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(decode("Zm9vY").is_none());
+        assert!(decode("Zm9v!A==").is_none());
+        assert!(decode("Zg===").is_none());
+        assert!(decode("Z=g=").is_none());
+    }
+}
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use eyre::{Context, Result};
+
+use crate::env::cli_or_env;
+
+// Debug builds embed the development build, it has the runtime warnings and devtools hooks
+#[cfg(debug_assertions)]
+const EMBEDDED: &str = include_str!("../assets/vue.global.js");
+// and release builds the production one
+#[cfg(not(debug_assertions))]
+const EMBEDDED: &str = include_str!("../assets/vue.global.prod.js");
+// Pinia's global build (https://unpkg.com/pinia@2/dist/pinia.iife.js), shipped next to the
+// executable or in `assets`
+const PINIA_FILE: &str = "pinia.iife.js";
+
+// The global builds of Pinia 2 import Vue through vue-demi's `VueDemi` global, on Vue 3 that's Vue
+// itself plus the Vue 2 compatibility helpers
+const VUE_DEMI: &str = r#"
+globalThis.VueDemi = {
+    ...Vue,
+    Vue,
+    Vue2: undefined,
+    isVue2: false,
+    isVue3: true,
+    install() {},
+    set(target, key, value) {
+        if (Array.isArray(target)) {
+            target.length = Math.max(target.length, key);
+            target.splice(key, 1, value);
+            return value;
+        }
+        target[key] = value;
+        return value;
+    },
+    del(target, key) {
+        if (Array.isArray(target)) {
+            target.splice(key, 1);
+            return;
+        }
+        delete target[key];
+    },
+};
+"#;
+
+// Where a file shipped with the app is looked for: next to the executable, in `assets` next to
+// it and in the source tree's `assets`
+fn asset_candidates(file: &str) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(PathBuf::from))
+    {
+        candidates.push(dir.join(file));
+        candidates.push(dir.join("assets").join(file));
+    }
+    candidates.push(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("assets")
+            .join(file),
+    );
+    candidates
+}
+
+// The `VueDemi` shim and Pinia, to be evaluated after Vue, `None` when Pinia isn't shipped (the
+// app runs without stores then). `--pinia <path>` or `VGUI_PINIA` point at another build
+pub fn pinia_source() -> Result<Option<String>> {
+    let path = match cli_or_env("--pinia", "VGUI_PINIA") {
+        Some(path) => PathBuf::from(path),
+        None => match asset_candidates(PINIA_FILE)
+            .into_iter()
+            .find(|path| path.is_file())
+        {
+            Some(path) => path,
+            None => {
+                println!(
+                    "{} not found next to the executable or in assets, Pinia and vgui.stores are disabled",
+                    PINIA_FILE
+                );
+                return Ok(None);
+            }
+        },
+    };
+    let pinia = std::fs::read_to_string(&path)
+        .wrap_err_with(|| format!("Failed to read Pinia bundle {}", path.display()))?;
+    Ok(Some(format!("{}\n{}", VUE_DEMI, pinia)))
+}
+
+// The Vue build scripts run against, it has to be a global build that includes the template
+// compiler (`vue.global.js` or `vue.global.prod.js`)
+#[derive(Debug, Clone)]
+pub enum VueBundle {
+    Embedded,
+    File(PathBuf),
+}
+
+impl VueBundle {
+    // `--vue <path>` on the command line, then the `VGUI_VUE` environment variable, otherwise
+    // the embedded build
+    pub fn from_env() -> Self {
+        match cli_or_env("--vue", "VGUI_VUE") {
+            Some(path) => Self::File(path.into()),
+            None => Self::Embedded,
+        }
+    }
+
+    pub fn source(&self) -> Result<String> {
+        match self {
+            VueBundle::Embedded => Ok(EMBEDDED.to_string()),
+            VueBundle::File(path) => std::fs::read_to_string(path)
+                .wrap_err_with(|| format!("Failed to read Vue bundle {}", path.display())),
+        }
+    }
+}
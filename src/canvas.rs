@@ -0,0 +1,245 @@
+use std::{cell::RefCell, rc::Rc};
+
+use eframe::egui::{self, Color32, Pos2, Stroke};
+use mini_v8::{Function, MiniV8, Object, Value, Variadic};
+
+use crate::value_to_color32;
+
+const DEFAULT_COLOR: Color32 = Color32::GRAY;
+
+#[derive(Debug, Clone)]
+pub enum DrawCommand {
+    Line {
+        from: Pos2,
+        to: Pos2,
+        stroke: Stroke,
+    },
+    Rect {
+        rect: egui::Rect,
+        rounding: f32,
+        fill: Option<Color32>,
+        stroke: Option<Stroke>,
+    },
+    Circle {
+        center: Pos2,
+        radius: f32,
+        fill: Option<Color32>,
+        stroke: Option<Stroke>,
+    },
+    Text {
+        pos: Pos2,
+        text: String,
+        size: f32,
+        color: Color32,
+    },
+}
+
+// Style options passed as the last argument of every drawing call
+// e.g. `ctx.rect(0, 0, 10, 10, { fill: [255, 0, 0], stroke: [0, 0, 0], strokeWidth: 2 })`
+struct DrawStyle {
+    color: Option<Color32>,
+    fill: Option<Color32>,
+    stroke: Option<Color32>,
+    width: f32,
+    rounding: f32,
+    size: f32,
+}
+
+impl DrawStyle {
+    fn from_object(isolate: &MiniV8, style: Option<Object>) -> mini_v8::Result<Self> {
+        let mut draw_style = DrawStyle {
+            color: None,
+            fill: None,
+            stroke: None,
+            width: 1.0,
+            rounding: 0.0,
+            size: 14.0,
+        };
+        let Some(style) = style else {
+            return Ok(draw_style);
+        };
+
+        let color = |key: &str| -> mini_v8::Result<Option<Color32>> {
+            let value: Value = style.get(key)?;
+            if value.is_undefined() || value.is_null() {
+                return Ok(None);
+            }
+            value_to_color32(isolate, &value)
+                .map(Some)
+                .map_err(|e| mini_v8::Error::ExternalError(format!("{:?}", e).into()))
+        };
+        draw_style.color = color("color")?;
+        draw_style.fill = color("fill")?;
+        draw_style.stroke = color("stroke")?;
+        if let Some(width) = style.get::<_, Option<f64>>("strokeWidth")? {
+            draw_style.width = width as f32;
+        }
+        if let Some(width) = style.get::<_, Option<f64>>("width")? {
+            draw_style.width = width as f32;
+        }
+        if let Some(rounding) = style.get::<_, Option<f64>>("rounding")? {
+            draw_style.rounding = rounding as f32;
+        }
+        if let Some(size) = style.get::<_, Option<f64>>("size")? {
+            draw_style.size = size as f32;
+        }
+        Ok(draw_style)
+    }
+
+    fn stroke(&self) -> Option<Stroke> {
+        self.stroke.map(|color| Stroke::new(self.width, color))
+    }
+}
+
+// Drawing context handed to the JS `onDraw` callback of canvas elements
+// The callback records draw commands every frame, which are then replayed onto the painter
+pub struct CanvasContext {
+    object: Object,
+    commands: Rc<RefCell<Vec<DrawCommand>>>,
+}
+
+impl CanvasContext {
+    pub fn new(isolate: &MiniV8) -> Self {
+        let object = isolate.create_object();
+        let commands = Rc::new(RefCell::new(Vec::new()));
+
+        // line(x1, y1, x2, y2, { color, width })
+        let commands_clone = commands.clone();
+        let line = isolate.create_function(move |invocation| {
+            let (x1, y1, x2, y2, style): (f32, f32, f32, f32, Option<Object>) =
+                invocation.args.into(&invocation.mv8)?;
+            let style = DrawStyle::from_object(&invocation.mv8, style)?;
+            commands_clone.borrow_mut().push(DrawCommand::Line {
+                from: egui::pos2(x1, y1),
+                to: egui::pos2(x2, y2),
+                stroke: Stroke::new(style.width, style.color.unwrap_or(DEFAULT_COLOR)),
+            });
+            Ok(())
+        });
+        object.set("line", line).expect("Failed to set line");
+
+        // rect(x, y, width, height, { fill, stroke, strokeWidth, rounding })
+        let commands_clone = commands.clone();
+        let rect = isolate.create_function(move |invocation| {
+            let (x, y, width, height, style): (f32, f32, f32, f32, Option<Object>) =
+                invocation.args.into(&invocation.mv8)?;
+            let style = DrawStyle::from_object(&invocation.mv8, style)?;
+            let fill = style.fill.or(style.color);
+            commands_clone.borrow_mut().push(DrawCommand::Rect {
+                rect: egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(width, height)),
+                rounding: style.rounding,
+                fill: if fill.is_none() && style.stroke.is_none() {
+                    Some(DEFAULT_COLOR)
+                } else {
+                    fill
+                },
+                stroke: style.stroke(),
+            });
+            Ok(())
+        });
+        object.set("rect", rect).expect("Failed to set rect");
+
+        // circle(x, y, radius, { fill, stroke, strokeWidth })
+        let commands_clone = commands.clone();
+        let circle = isolate.create_function(move |invocation| {
+            let (x, y, radius, style): (f32, f32, f32, Option<Object>) =
+                invocation.args.into(&invocation.mv8)?;
+            let style = DrawStyle::from_object(&invocation.mv8, style)?;
+            let fill = style.fill.or(style.color);
+            commands_clone.borrow_mut().push(DrawCommand::Circle {
+                center: egui::pos2(x, y),
+                radius,
+                fill: if fill.is_none() && style.stroke.is_none() {
+                    Some(DEFAULT_COLOR)
+                } else {
+                    fill
+                },
+                stroke: style.stroke(),
+            });
+            Ok(())
+        });
+        object.set("circle", circle).expect("Failed to set circle");
+
+        // text(x, y, text, { color, size })
+        let commands_clone = commands.clone();
+        let text = isolate.create_function(move |invocation| {
+            let (x, y, text, style): (f32, f32, String, Option<Object>) =
+                invocation.args.into(&invocation.mv8)?;
+            let style = DrawStyle::from_object(&invocation.mv8, style)?;
+            commands_clone.borrow_mut().push(DrawCommand::Text {
+                pos: egui::pos2(x, y),
+                text,
+                size: style.size,
+                color: style.color.unwrap_or(DEFAULT_COLOR),
+            });
+            Ok(())
+        });
+        object.set("text", text).expect("Failed to set text");
+
+        Self { object, commands }
+    }
+
+    // Calls the draw callback and returns the commands it recorded
+    pub fn record(&self, draw: &Function, size: egui::Vec2) -> mini_v8::Result<Vec<DrawCommand>> {
+        self.commands.borrow_mut().clear();
+        self.object.set("width", size.x)?;
+        self.object.set("height", size.y)?;
+        draw.call::<Variadic<Value>, ()>(Variadic::from_vec(vec![Value::Object(
+            self.object.clone(),
+        )]))?;
+        Ok(self.commands.borrow_mut().drain(..).collect())
+    }
+}
+
+// Replays the recorded commands, positions are relative to the canvas origin
+pub fn paint(painter: &egui::Painter, origin: Pos2, commands: &[DrawCommand]) {
+    let offset = origin.to_vec2();
+    for command in commands {
+        match command {
+            DrawCommand::Line { from, to, stroke } => {
+                painter.line_segment([*from + offset, *to + offset], *stroke);
+            }
+            DrawCommand::Rect {
+                rect,
+                rounding,
+                fill,
+                stroke,
+            } => {
+                let rect = rect.translate(offset);
+                if let Some(fill) = fill {
+                    painter.rect_filled(rect, *rounding, *fill);
+                }
+                if let Some(stroke) = stroke {
+                    painter.rect_stroke(rect, *rounding, *stroke);
+                }
+            }
+            DrawCommand::Circle {
+                center,
+                radius,
+                fill,
+                stroke,
+            } => {
+                if let Some(fill) = fill {
+                    painter.circle_filled(*center + offset, *radius, *fill);
+                }
+                if let Some(stroke) = stroke {
+                    painter.circle_stroke(*center + offset, *radius, *stroke);
+                }
+            }
+            DrawCommand::Text {
+                pos,
+                text,
+                size,
+                color,
+            } => {
+                painter.text(
+                    *pos + offset,
+                    egui::Align2::LEFT_TOP,
+                    text,
+                    egui::FontId::proportional(*size),
+                    *color,
+                );
+            }
+        }
+    }
+}
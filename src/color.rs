@@ -0,0 +1,188 @@
+use eframe::egui::Color32;
+
+// Named colors accepted in color props, a subset of the CSS color keywords
+const NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("black", [0, 0, 0]),
+    ("silver", [192, 192, 192]),
+    ("gray", [128, 128, 128]),
+    ("grey", [128, 128, 128]),
+    ("white", [255, 255, 255]),
+    ("maroon", [128, 0, 0]),
+    ("red", [255, 0, 0]),
+    ("purple", [128, 0, 128]),
+    ("fuchsia", [255, 0, 255]),
+    ("magenta", [255, 0, 255]),
+    ("green", [0, 128, 0]),
+    ("lime", [0, 255, 0]),
+    ("olive", [128, 128, 0]),
+    ("yellow", [255, 255, 0]),
+    ("navy", [0, 0, 128]),
+    ("blue", [0, 0, 255]),
+    ("teal", [0, 128, 128]),
+    ("aqua", [0, 255, 255]),
+    ("cyan", [0, 255, 255]),
+    ("orange", [255, 165, 0]),
+    ("gold", [255, 215, 0]),
+    ("pink", [255, 192, 203]),
+    ("brown", [165, 42, 42]),
+    ("indigo", [75, 0, 130]),
+    ("violet", [238, 130, 238]),
+    ("crimson", [220, 20, 60]),
+    ("coral", [255, 127, 80]),
+    ("tomato", [255, 99, 71]),
+    ("salmon", [250, 128, 114]),
+    ("khaki", [240, 230, 140]),
+    ("beige", [245, 245, 220]),
+    ("turquoise", [64, 224, 208]),
+    ("skyblue", [135, 206, 235]),
+    ("steelblue", [70, 130, 180]),
+    ("royalblue", [65, 105, 225]),
+    ("darkblue", [0, 0, 139]),
+    ("darkred", [139, 0, 0]),
+    ("darkgreen", [0, 100, 0]),
+    ("darkgray", [169, 169, 169]),
+    ("darkgrey", [169, 169, 169]),
+    ("lightgray", [211, 211, 211]),
+    ("lightgrey", [211, 211, 211]),
+    ("lightblue", [173, 216, 230]),
+    ("lightgreen", [144, 238, 144]),
+];
+
+// Parses a CSS color: `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb(r, g, b)`,
+// `rgba(r, g, b, a)` (alpha in 0-1 or a percentage) and named colors
+pub fn parse_css_color(color: &str) -> Result<Color32, String> {
+    let color = color.trim().to_lowercase();
+    if let Some(hex) = color.strip_prefix('#') {
+        return parse_hex(hex).ok_or_else(|| format!("Invalid hex color: {}", color));
+    }
+    if let Some(arguments) = color
+        .strip_prefix("rgba(")
+        .or_else(|| color.strip_prefix("rgb("))
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return parse_rgb(arguments).ok_or_else(|| format!("Invalid rgb color: {}", color));
+    }
+    if color == "transparent" {
+        return Ok(Color32::TRANSPARENT);
+    }
+    NAMED_COLORS
+        .iter()
+        .find(|(name, _)| *name == color)
+        .map(|(_, [r, g, b])| Color32::from_rgb(*r, *g, *b))
+        .ok_or_else(|| format!("Unknown color: {}", color))
+}
+
+fn parse_hex(hex: &str) -> Option<Color32> {
+    let digits: Vec<u8> = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()?;
+    let channels: Vec<u8> = match digits.len() {
+        // short form, every digit is doubled
+        3 | 4 => digits.iter().map(|digit| digit * 17).collect(),
+        6 | 8 => digits
+            .chunks(2)
+            .map(|pair| pair[0] * 16 + pair[1])
+            .collect(),
+        _ => return None,
+    };
+    let alpha = channels.get(3).copied().unwrap_or(255);
+    Some(Color32::from_rgba_unmultiplied(
+        channels[0],
+        channels[1],
+        channels[2],
+        alpha,
+    ))
+}
+
+fn parse_rgb(arguments: &str) -> Option<Color32> {
+    // both the legacy `rgb(1, 2, 3)` and the modern `rgb(1 2 3 / 50%)` syntax
+    let parts: Vec<&str> = arguments
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts.len() != 3 && parts.len() != 4 {
+        return None;
+    }
+    let channel = |part: &str| -> Option<u8> {
+        let value = match part.strip_suffix('%') {
+            Some(percentage) => percentage.parse::<f32>().ok()? / 100.0 * 255.0,
+            None => part.parse::<f32>().ok()?,
+        };
+        Some(value.round().clamp(0.0, 255.0) as u8)
+    };
+    let alpha = match parts.get(3) {
+        Some(part) => {
+            let value = match part.strip_suffix('%') {
+                Some(percentage) => percentage.parse::<f32>().ok()? / 100.0,
+                None => part.parse::<f32>().ok()?,
+            };
+            (value.clamp(0.0, 1.0) * 255.0).round() as u8
+        }
+        None => 255,
+    };
+    Some(Color32::from_rgba_unmultiplied(
+        channel(parts[0])?,
+        channel(parts[1])?,
+        channel(parts[2])?,
+        alpha,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba(r: u8, g: u8, b: u8, a: u8) -> Result<Color32, String> {
+        Ok(Color32::from_rgba_unmultiplied(r, g, b, a))
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_css_color("#f00"), rgba(255, 0, 0, 255));
+        assert_eq!(parse_css_color("#f008"), rgba(255, 0, 0, 136));
+        assert_eq!(parse_css_color("#1A2b3c"), rgba(26, 43, 60, 255));
+        assert_eq!(parse_css_color("#1a2b3c80"), rgba(26, 43, 60, 128));
+    }
+
+    #[test]
+    fn parses_rgb_functions() {
+        assert_eq!(parse_css_color("rgb(1, 2, 3)"), rgba(1, 2, 3, 255));
+        assert_eq!(parse_css_color("rgba(1, 2, 3, 0.5)"), rgba(1, 2, 3, 128));
+        assert_eq!(parse_css_color("rgb(1 2 3 / 50%)"), rgba(1, 2, 3, 128));
+        assert_eq!(
+            parse_css_color("rgb(100%, 50%, 0%)"),
+            rgba(255, 128, 0, 255)
+        );
+        // out of range channels and alpha are clamped
+        assert_eq!(
+            parse_css_color("rgba(300, -20, 0, 2)"),
+            rgba(255, 0, 0, 255)
+        );
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!(parse_css_color("transparent"), Ok(Color32::TRANSPARENT));
+        assert_eq!(parse_css_color(" Teal "), rgba(0, 128, 128, 255));
+        assert_eq!(parse_css_color("grey"), parse_css_color("gray"));
+    }
+
+    #[test]
+    fn rejects_invalid_colors() {
+        for color in [
+            "#12345",
+            "#ggg",
+            "rgb(1,2)",
+            "rgb(1,2,3,4,5)",
+            "rgb(a, b, c)",
+            "blurple",
+        ] {
+            assert!(
+                parse_css_color(color).is_err(),
+                "{} should be rejected",
+                color
+            );
+        }
+    }
+}
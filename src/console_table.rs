@@ -0,0 +1,93 @@
+// Lays out `console.table` output as an ASCII table, cells come from `value_to_string`
+fn visible_width(text: &str) -> usize {
+    text.chars().count()
+}
+
+// Multi-line cells (larger objects) are folded into one line
+fn single_line(cell: &str) -> String {
+    cell.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn render(headers: &[String], rows: &[Vec<String>]) -> String {
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| single_line(cell)).collect())
+        .collect();
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| visible_width(cell))
+                .chain([visible_width(header)])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let border = widths
+        .iter()
+        .map(|width| "-".repeat(width + 2))
+        .collect::<Vec<_>>()
+        .join("+");
+    let border = format!("+{}+", border);
+    let line = |cells: &[String]| {
+        let cells = widths
+            .iter()
+            .enumerate()
+            .map(|(column, width)| {
+                let cell = cells.get(column).map(String::as_str).unwrap_or("");
+                format!(" {}{} ", cell, " ".repeat(width - visible_width(cell)))
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+        format!("|{}|", cells)
+    };
+
+    let mut table = vec![border.clone(), line(headers), border.clone()];
+    table.extend(rows.iter().map(|row| line(row)));
+    table.push(border);
+    table.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn aligns_columns() {
+        let table = render(
+            &strings(&["(index)", "name", "age"]),
+            &[
+                strings(&["0", "\"Ada\"", "36"]),
+                strings(&["1", "\"Grace\""]),
+            ],
+        );
+        assert_eq!(
+            table,
+            [
+                "+---------+---------+-----+",
+                "| (index) | name    | age |",
+                "+---------+---------+-----+",
+                "| 0       | \"Ada\"   | 36  |",
+                "| 1       | \"Grace\" |     |",
+                "+---------+---------+-----+",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn folds_lines() {
+        assert_eq!(single_line("{\n  a: 1,\n  b: 2\n}"), "{ a: 1, b: 2 }");
+    }
+}
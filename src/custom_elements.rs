@@ -0,0 +1,224 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use eframe::egui::Color32;
+use mini_v8::{Error as MiniV8Error, Function, MiniV8, Object, ToValue, Value, Variadic};
+
+use crate::{value_to_color32, ElementId};
+
+// What the `render` function of a registered element asked for, replayed onto the ui in order
+#[derive(Debug, Clone)]
+pub enum UiCommand {
+    Label {
+        text: String,
+        color: Option<Color32>,
+        size: Option<f32>,
+        strong: bool,
+    },
+    Space(f32),
+    Separator,
+    // the element's own children, all of them or one
+    Children,
+    Child(ElementId),
+    Horizontal(Vec<UiCommand>),
+    Vertical(Vec<UiCommand>),
+    Frame {
+        fill: Option<Color32>,
+        stroke: Option<Color32>,
+        rounding: f32,
+        padding: f32,
+        commands: Vec<UiCommand>,
+    },
+}
+
+fn external_error(message: String) -> MiniV8Error {
+    MiniV8Error::ExternalError(message.into())
+}
+
+fn optional_color(isolate: &MiniV8, style: &Object, key: &str) -> mini_v8::Result<Option<Color32>> {
+    let value: Value = style.get(key)?;
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+    value_to_color32(isolate, &value)
+        .map(Some)
+        .map_err(|e| external_error(format!("{:?}", e)))
+}
+
+// The `vgui.registerElement(tag, { render(props, children, ui) })` registry, `ui` records the
+// commands a render makes, nested layouts call back into their builder while recording
+pub struct CustomElements {
+    register: Function,
+    ui: Object,
+    renderers: Rc<RefCell<HashMap<String, Function>>>,
+    // a stack of command lists, nested layouts record into a list of their own
+    recording: Rc<RefCell<Vec<Vec<UiCommand>>>>,
+}
+
+impl CustomElements {
+    pub fn new(isolate: &MiniV8) -> Self {
+        let renderers: Rc<RefCell<HashMap<String, Function>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let recording: Rc<RefCell<Vec<Vec<UiCommand>>>> = Rc::new(RefCell::new(Vec::new()));
+
+        // registerElement('badge', { render(props, children, ui) { ... } })
+        let renderers_clone = renderers.clone();
+        let register = isolate.create_function(move |invocation| {
+            let (tag, definition): (String, Object) = invocation.args.into(&invocation.mv8)?;
+            let render: Function = definition
+                .get("render")
+                .map_err(|_| external_error(format!("Element {} has no render function", tag)))?;
+            let mut renderers = renderers_clone.borrow_mut();
+            if renderers.contains_key(&tag) {
+                return Err(external_error(format!(
+                    "Element {} is already registered",
+                    tag
+                )));
+            }
+            renderers.insert(tag, render);
+            Ok(())
+        });
+
+        let ui = isolate.create_object();
+        let push = {
+            let recording = recording.clone();
+            move |command: UiCommand| -> mini_v8::Result<()> {
+                recording
+                    .borrow_mut()
+                    .last_mut()
+                    .ok_or_else(|| external_error("ui is only usable while rendering".into()))?
+                    .push(command);
+                Ok(())
+            }
+        };
+
+        // label(text, { color, size, strong })
+        let push_clone = push.clone();
+        let label = isolate.create_function(move |invocation| {
+            let (text, style): (String, Option<Object>) = invocation.args.into(&invocation.mv8)?;
+            let (color, size, strong) = match style {
+                Some(style) => (
+                    optional_color(&invocation.mv8, &style, "color")?,
+                    style.get::<_, Option<f64>>("size")?.map(|size| size as f32),
+                    style.get::<_, Option<bool>>("strong")?.unwrap_or(false),
+                ),
+                None => (None, None, false),
+            };
+            push_clone(UiCommand::Label {
+                text,
+                color,
+                size,
+                strong,
+            })
+        });
+        ui.set("label", label).expect("Failed to set ui.label");
+
+        // space(amount)
+        let push_clone = push.clone();
+        let space = isolate.create_function(move |invocation| {
+            let (amount,): (f64,) = invocation.args.into(&invocation.mv8)?;
+            push_clone(UiCommand::Space(amount as f32))
+        });
+        ui.set("space", space).expect("Failed to set ui.space");
+
+        // separator()
+        let push_clone = push.clone();
+        let separator = isolate.create_function(move |_| push_clone(UiCommand::Separator));
+        ui.set("separator", separator)
+            .expect("Failed to set ui.separator");
+
+        // children() renders all children, child(id) one of the ids passed to render
+        let push_clone = push.clone();
+        let children = isolate.create_function(move |_| push_clone(UiCommand::Children));
+        ui.set("children", children)
+            .expect("Failed to set ui.children");
+        let push_clone = push.clone();
+        let child = isolate.create_function(move |invocation| {
+            let (id,): (ElementId,) = invocation.args.into(&invocation.mv8)?;
+            push_clone(UiCommand::Child(id))
+        });
+        ui.set("child", child).expect("Failed to set ui.child");
+
+        // horizontal(() => { ... }), vertical(() => { ... }) and
+        // frame({ fill, stroke, rounding, padding }, () => { ... })
+        let nested = {
+            let recording = recording.clone();
+            move |build: Function| -> mini_v8::Result<Vec<UiCommand>> {
+                recording.borrow_mut().push(Vec::new());
+                let result = build.call::<(), ()>(());
+                let commands = recording.borrow_mut().pop().unwrap_or_default();
+                result.map(|_| commands)
+            }
+        };
+        let nested_clone = nested.clone();
+        let push_clone = push.clone();
+        let horizontal = isolate.create_function(move |invocation| {
+            let (build,): (Function,) = invocation.args.into(&invocation.mv8)?;
+            push_clone(UiCommand::Horizontal(nested_clone(build)?))
+        });
+        ui.set("horizontal", horizontal)
+            .expect("Failed to set ui.horizontal");
+        let nested_clone = nested.clone();
+        let push_clone = push.clone();
+        let vertical = isolate.create_function(move |invocation| {
+            let (build,): (Function,) = invocation.args.into(&invocation.mv8)?;
+            push_clone(UiCommand::Vertical(nested_clone(build)?))
+        });
+        ui.set("vertical", vertical)
+            .expect("Failed to set ui.vertical");
+        let frame = isolate.create_function(move |invocation| {
+            let (style, build): (Object, Function) = invocation.args.into(&invocation.mv8)?;
+            let fill = optional_color(&invocation.mv8, &style, "fill")?;
+            let stroke = optional_color(&invocation.mv8, &style, "stroke")?;
+            let rounding = style.get::<_, Option<f64>>("rounding")?.unwrap_or(0.0) as f32;
+            let padding = style.get::<_, Option<f64>>("padding")?.unwrap_or(0.0) as f32;
+            push(UiCommand::Frame {
+                fill,
+                stroke,
+                rounding,
+                padding,
+                commands: nested(build)?,
+            })
+        });
+        ui.set("frame", frame).expect("Failed to set ui.frame");
+
+        Self {
+            register,
+            ui,
+            renderers,
+            recording,
+        }
+    }
+
+    pub fn register_function(&self) -> Function {
+        self.register.clone()
+    }
+
+    pub fn is_registered(&self, tag: &str) -> bool {
+        self.renderers.borrow().contains_key(tag)
+    }
+
+    // Calls the element's render function, `props` are its attributes
+    pub fn record(
+        &self,
+        isolate: &MiniV8,
+        tag: &str,
+        props: Object,
+        children: Vec<ElementId>,
+    ) -> mini_v8::Result<Vec<UiCommand>> {
+        let render = self
+            .renderers
+            .borrow()
+            .get(tag)
+            .cloned()
+            .ok_or_else(|| external_error(format!("Unknown element: {}", tag)))?;
+        let children = children.to_value(isolate)?;
+        self.recording.borrow_mut().push(Vec::new());
+        let result = render.call::<Variadic<Value>, ()>(Variadic::from_vec(vec![
+            Value::Object(props),
+            children,
+            Value::Object(self.ui.clone()),
+        ]));
+        let commands = self.recording.borrow_mut().pop().unwrap_or_default();
+        result.map(|_| commands)
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use mini_v8::{Function, MiniV8, Object};
+
+// The value of `--flag value` or `--flag=value` in `args`
+fn flag_value(args: impl IntoIterator<Item = String>, flag: &str) -> Option<String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg
+            .strip_prefix(flag)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_string());
+        }
+        if arg == flag {
+            return args.next();
+        }
+    }
+    None
+}
+
+// A setting given on the command line as `--flag value` or `--flag=value`, otherwise taken from
+// the environment variable `var`
+pub fn cli_or_env(flag: &str, var: &str) -> Option<String> {
+    flag_value(std::env::args().skip(1), flag).or_else(|| std::env::var(var).ok())
+}
+
+// `--env-allow <names>` on the command line or the `VGUI_ENV_ALLOW` environment variable, a comma
+// separated list of the variables scripts may read, a trailing `*` matches a prefix. Without one
+// `vgui.env` has every variable
+fn allowlist_from_env() -> Option<Vec<String>> {
+    let list = cli_or_env("--env-allow", "VGUI_ENV_ALLOW")?;
+    Some(
+        list.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+fn is_allowed(name: &str, allowlist: &[String]) -> bool {
+    allowlist
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == pattern,
+        })
+}
+
+fn filter_vars(
+    vars: impl IntoIterator<Item = (String, String)>,
+    allowlist: Option<&[String]>,
+) -> BTreeMap<String, String> {
+    let Some(allowlist) = allowlist else {
+        return vars.into_iter().collect();
+    };
+    vars.into_iter()
+        .filter(|(name, _)| is_allowed(name, allowlist))
+        .collect()
+}
+
+// A frozen snapshot of the environment for `vgui.env`, variables that aren't valid unicode are
+// left out
+pub fn object(isolate: &MiniV8) -> Object {
+    let vars = std::env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)));
+    let vars = filter_vars(vars, allowlist_from_env().as_deref());
+    isolate
+        .eval::<_, Function>("(vars) => Object.freeze(Object.assign({}, vars))")
+        .and_then(|freeze| freeze.call::<_, Object>((vars,)))
+        .expect("Failed to create env")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> Vec<(String, String)> {
+        [
+            ("API_KEY", "secret"),
+            ("HTTP_PROXY", "proxy:8080"),
+            ("HOME", "/home/me"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .to_vec()
+    }
+
+    #[test]
+    fn reads_flag_values() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            flag_value(args(&["--vue", "a.js", "--app=main.js"]), "--app"),
+            Some("main.js".to_string())
+        );
+        assert_eq!(
+            flag_value(args(&["--app", "main.js"]), "--app"),
+            Some("main.js".to_string())
+        );
+        // a flag that only shares the prefix doesn't count
+        assert_eq!(flag_value(args(&["--apple=1", "--app"]), "--app"), None);
+    }
+
+    #[test]
+    fn keeps_everything_without_an_allowlist() {
+        assert_eq!(filter_vars(vars(), None).len(), 3);
+    }
+
+    #[test]
+    fn filters_by_name_and_prefix() {
+        let allowlist = ["API_KEY".to_string(), "HTTP_*".to_string()];
+        let vars = filter_vars(vars(), Some(&allowlist));
+        assert_eq!(vars.keys().collect::<Vec<_>>(), ["API_KEY", "HTTP_PROXY"]);
+        assert!(filter_vars(self::vars(), Some(&[])).is_empty());
+    }
+}
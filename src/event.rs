@@ -0,0 +1,129 @@
+use eframe::egui;
+use mini_v8::{Error as MiniV8Error, Function, MiniV8, Object, ToValue, Value, Variadic};
+
+use crate::{ElementId, KeyEvent};
+
+// Builds the event object passed to handlers, e.g.
+// `{ type: 'click', target: 3, x: 120, y: 48, ctrl: false, shift: false, alt: false, timestamp: 1520 }`
+pub struct EventBuilder {
+    object: Object,
+}
+
+impl EventBuilder {
+    pub fn new(isolate: &MiniV8, event_type: &str, target: ElementId) -> Self {
+        let object = isolate.create_object();
+        object
+            .set("type", event_type)
+            .expect("Failed to set event type");
+        object
+            .set("target", target as f64)
+            .expect("Failed to set event target");
+        Self { object }
+    }
+
+    // Pointer position, modifiers and the time since startup in milliseconds
+    pub fn input(self, ctx: &egui::Context) -> Self {
+        let (position, modifiers, time) =
+            ctx.input(|i| (i.pointer.latest_pos(), i.modifiers, i.time));
+        match position {
+            Some(position) => {
+                self.object.set("x", position.x).expect("Failed to set x");
+                self.object.set("y", position.y).expect("Failed to set y");
+            }
+            None => {
+                self.object.set("x", Value::Null).expect("Failed to set x");
+                self.object.set("y", Value::Null).expect("Failed to set y");
+            }
+        }
+        self.object
+            .set("timestamp", time * 1000.0)
+            .expect("Failed to set timestamp");
+        self.modifiers(modifiers)
+    }
+
+    // The pressed key, with the modifiers held when it was pressed
+    pub fn key(self, event: &KeyEvent) -> Self {
+        self.object
+            .set("key", event.key.name())
+            .expect("Failed to set key");
+        self.modifiers(event.modifiers)
+    }
+
+    // The dragged element of a drop
+    pub fn source(self, source: ElementId) -> Self {
+        self.object
+            .set("source", source as f64)
+            .expect("Failed to set event source");
+        self
+    }
+
+    // Any other field, for payloads specific to one event type
+    pub fn field(self, key: &str, value: impl ToValue) -> Self {
+        self.object
+            .set(key, value)
+            .expect("Failed to set event field");
+        self
+    }
+
+    pub fn value(self, value: Value) -> Self {
+        self.object
+            .set("value", value)
+            .expect("Failed to set event value");
+        self
+    }
+
+    fn modifiers(self, modifiers: egui::Modifiers) -> Self {
+        self.object
+            .set("ctrl", modifiers.ctrl)
+            .expect("Failed to set ctrl");
+        self.object
+            .set("shift", modifiers.shift)
+            .expect("Failed to set shift");
+        self.object
+            .set("alt", modifiers.alt)
+            .expect("Failed to set alt");
+        self
+    }
+
+    pub fn build(self) -> Value {
+        Value::Object(self.object)
+    }
+}
+
+// Calls an event handler, an exception thrown by the handler is reported instead of taking
+// the whole app down, and the handler's return value is `None`
+pub fn call_handler(
+    isolate: &MiniV8,
+    handler: &Function,
+    event_type: &str,
+    args: Vec<Value>,
+) -> Option<Value> {
+    match handler.call::<Variadic<Value>, Value>(Variadic::from_vec(args)) {
+        Ok(result) => Some(result),
+        Err(error) => {
+            report_error(isolate, event_type, error);
+            None
+        }
+    }
+}
+
+// Logs a handler's error through `console.error`, so it shows up next to the script's own logs
+pub fn report_error(isolate: &MiniV8, event_type: &str, error: MiniV8Error) {
+    let message = format!("Uncaught error in {} handler:", event_type);
+    log_error(isolate, message, error.to_value(isolate));
+}
+
+// Logs the reason of a promise nothing handled the rejection of, like an `async` handler's error
+pub fn report_unhandled_rejection(isolate: &MiniV8, reason: Value) {
+    log_error(isolate, "Uncaught (in promise)".to_string(), reason);
+}
+
+fn log_error(isolate: &MiniV8, message: String, error: Value) {
+    let reported = isolate
+        .global()
+        .get::<_, Object>("console")
+        .and_then(|console| console.call_prop::<_, _, ()>("error", (message.clone(), error)));
+    if let Err(e) = reported {
+        println!("{} {:?}", message, e);
+    }
+}
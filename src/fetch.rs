@@ -0,0 +1,291 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    sync::mpsc::{self, Receiver},
+};
+
+use eframe::egui;
+use mini_v8::{Function, MiniV8, ToValue, ToValues};
+
+use crate::event::call_handler;
+
+// `Headers`, `Response` and `fetch()` itself, the request goes through `send(url, method,
+// headers, body, resolve, reject)` and `resolve(status, statusText, url, redirected, headers,
+// body)` builds the response
+const PRELUDE: &str = r#"
+(send) => {
+    const normalize = (name) => String(name).toLowerCase();
+
+    class Headers {
+        constructor(init) {
+            this._map = new Map();
+            if (init instanceof Headers || Array.isArray(init)) {
+                for (const [name, value] of init) {
+                    this.append(name, value);
+                }
+            } else if (init) {
+                for (const name of Object.keys(init)) {
+                    this.append(name, init[name]);
+                }
+            }
+        }
+        append(name, value) {
+            const existing = this._map.get(normalize(name));
+            this._map.set(normalize(name),
+                existing === undefined ? String(value) : existing + ', ' + value);
+        }
+        set(name, value) {
+            this._map.set(normalize(name), String(value));
+        }
+        get(name) {
+            const value = this._map.get(normalize(name));
+            return value === undefined ? null : value;
+        }
+        has(name) {
+            return this._map.has(normalize(name));
+        }
+        delete(name) {
+            this._map.delete(normalize(name));
+        }
+        forEach(callback, thisArg) {
+            for (const [name, value] of this) {
+                callback.call(thisArg, value, name, this);
+            }
+        }
+        entries() {
+            return [...this._map.entries()]
+                .sort(([a], [b]) => (a < b ? -1 : a > b ? 1 : 0))[Symbol.iterator]();
+        }
+        keys() {
+            return [...this.entries()].map(([name]) => name)[Symbol.iterator]();
+        }
+        values() {
+            return [...this.entries()].map(([, value]) => value)[Symbol.iterator]();
+        }
+        [Symbol.iterator]() {
+            return this.entries();
+        }
+    }
+
+    class Response {
+        constructor(body, init = {}) {
+            this._body = body == null ? '' : String(body);
+            this.status = init.status === undefined ? 200 : init.status;
+            this.statusText = init.statusText || '';
+            this.ok = this.status >= 200 && this.status < 300;
+            this.headers = new Headers(init.headers);
+            this.url = '';
+            this.redirected = false;
+            this.bodyUsed = false;
+        }
+        _consume() {
+            if (this.bodyUsed) {
+                return Promise.reject(new TypeError('Body has already been consumed'));
+            }
+            this.bodyUsed = true;
+            return Promise.resolve(this._body);
+        }
+        text() {
+            return this._consume();
+        }
+        json() {
+            return this._consume().then((text) => JSON.parse(text));
+        }
+        clone() {
+            if (this.bodyUsed) {
+                throw new TypeError('Cannot clone a consumed response');
+            }
+            const response = new Response(this._body, this);
+            response.url = this.url;
+            response.redirected = this.redirected;
+            return response;
+        }
+        static json(data, init = {}) {
+            const headers = new Headers(init.headers);
+            if (!headers.has('content-type')) {
+                headers.set('content-type', 'application/json');
+            }
+            return new Response(JSON.stringify(data), { ...init, headers });
+        }
+    }
+
+    // fetch(url, { method, headers, body }), also takes `{ url, method, headers, body }`
+    // request objects and anything that turns into a URL string
+    function fetch(input, init = {}) {
+        return new Promise((resolve, reject) => {
+            const request = input !== null && typeof input === 'object' && 'url' in input
+                ? input
+                : { url: String(input) };
+            const method = String(init.method || request.method || 'GET').toUpperCase();
+            const headers = new Headers(init.headers || request.headers);
+            const body = init.body !== undefined ? init.body : request.body;
+            if (body != null && (method === 'GET' || method === 'HEAD')) {
+                throw new TypeError(method + ' requests cannot have a body');
+            }
+            if (body instanceof URLSearchParams && !headers.has('content-type')) {
+                headers.set('content-type', 'application/x-www-form-urlencoded;charset=UTF-8');
+            }
+            const sentHeaders = {};
+            headers.forEach((value, name) => {
+                sentHeaders[name] = value;
+            });
+            send(request.url, method, sentHeaders, body == null ? null : String(body),
+                (status, statusText, url, redirected, responseHeaders, text) => {
+                    const response = new Response(text, {
+                        status,
+                        statusText,
+                        headers: responseHeaders,
+                    });
+                    response.url = url;
+                    response.redirected = redirected;
+                    resolve(response);
+                },
+                (message) => reject(new TypeError('Failed to fetch: ' + message)));
+        });
+    }
+
+    globalThis.Headers = Headers;
+    globalThis.Response = Response;
+    globalThis.fetch = fetch;
+}
+"#;
+
+struct FetchedResponse {
+    status: u16,
+    status_text: String,
+    url: String,
+    redirected: bool,
+    headers: Vec<Vec<String>>,
+    body: String,
+}
+
+type Settled = (u32, Result<FetchedResponse, String>);
+
+// `fetch()`, requests run on the tokio runtime and their promises are settled on the UI thread
+// once the response body arrived
+pub struct Fetch {
+    pending: Rc<RefCell<HashMap<u32, (Function, Function)>>>,
+    settled: Receiver<Settled>,
+}
+
+async fn request(
+    client: reqwest::Client,
+    url: String,
+    method: String,
+    headers: HashMap<String, String>,
+    body: Option<String>,
+) -> Result<FetchedResponse, String> {
+    let method = reqwest::Method::from_bytes(method.as_bytes())
+        .map_err(|_| format!("Invalid method {}", method))?;
+    let url = reqwest::Url::parse(&url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+    let mut builder = client.request(method, url.clone());
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+    if let Some(body) = body {
+        builder = builder.body(body);
+    }
+
+    let response = builder.send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    let redirected = *response.url() != url;
+    let url = response.url().to_string();
+    let headers = response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            vec![
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            ]
+        })
+        .collect();
+    let body = response.bytes().await.map_err(|e| e.to_string())?;
+    Ok(FetchedResponse {
+        status: status.as_u16(),
+        status_text: status.canonical_reason().unwrap_or("").to_string(),
+        url,
+        redirected,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+impl Fetch {
+    pub fn new(isolate: &MiniV8, ctx: &egui::Context) -> Self {
+        let pending: Rc<RefCell<HashMap<u32, (Function, Function)>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let (sender, settled) = mpsc::channel::<Settled>();
+        let client = reqwest::Client::new();
+
+        let next_id = Cell::new(0);
+        let pending_clone = pending.clone();
+        let ctx = ctx.clone();
+        let send = isolate.create_function(move |invocation| {
+            let (url, method, headers, body, resolve, reject): (
+                String,
+                String,
+                HashMap<String, String>,
+                Option<String>,
+                Function,
+                Function,
+            ) = invocation.args.into(&invocation.mv8)?;
+            let id = next_id.get() + 1;
+            next_id.set(id);
+            pending_clone.borrow_mut().insert(id, (resolve, reject));
+
+            let client = client.clone();
+            let sender = sender.clone();
+            let ctx = ctx.clone();
+            tokio::spawn(async move {
+                let result = request(client, url, method, headers, body).await;
+                if sender.send((id, result)).is_ok() {
+                    ctx.request_repaint();
+                }
+            });
+            Ok(())
+        });
+
+        isolate
+            .eval::<_, Function>(PRELUDE)
+            .and_then(|prelude| prelude.call::<_, ()>((send,)))
+            .expect("Failed to set fetch");
+
+        Self { pending, settled }
+    }
+
+    // Settles the promises of the requests that finished since the last frame
+    pub fn run(&self, isolate: &MiniV8) {
+        for (id, result) in self.settled.try_iter() {
+            let Some((resolve, reject)) = self.pending.borrow_mut().remove(&id) else {
+                continue;
+            };
+            let args = match result {
+                Ok(response) => (
+                    resolve,
+                    (
+                        response.status,
+                        response.status_text,
+                        response.url,
+                        response.redirected,
+                        response.headers,
+                        response.body,
+                    )
+                        .to_values(isolate)
+                        .map(|values| values.into_vec()),
+                ),
+                Err(message) => (
+                    reject,
+                    message.to_value(isolate).map(|message| vec![message]),
+                ),
+            };
+            match args {
+                (settle, Ok(args)) => {
+                    call_handler(isolate, &settle, "fetch", args);
+                }
+                (_, Err(e)) => println!("Failed to settle fetch: {:?}", e),
+            }
+        }
+    }
+}
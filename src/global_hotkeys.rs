@@ -0,0 +1,142 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    sync::mpsc::{self, Receiver},
+};
+
+use eframe::egui;
+use global_hotkey::{hotkey::HotKey, GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use mini_v8::{Error as MiniV8Error, Function, MiniV8, Object};
+
+use crate::event::{call_handler, EventBuilder};
+
+struct RegisteredHotkey {
+    keys: String,
+    hotkey: HotKey,
+    handler: Function,
+}
+
+// The `vgui.globalHotkeys` object, hotkeys registered with the OS that fire even while the
+// window is unfocused or minimized
+pub struct GlobalHotkeys {
+    object: Object,
+    registered: Rc<RefCell<HashMap<u32, RegisteredHotkey>>>,
+    pressed: Receiver<u32>,
+}
+
+fn external_error(message: String) -> MiniV8Error {
+    MiniV8Error::ExternalError(message.into())
+}
+
+impl GlobalHotkeys {
+    pub fn new(isolate: &MiniV8, ctx: &egui::Context) -> Self {
+        let object = isolate.create_object();
+        let registered: Rc<RefCell<HashMap<u32, RegisteredHotkey>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+
+        // Not every platform has a backend (e.g. Wayland), registering then throws
+        let manager = match GlobalHotKeyManager::new() {
+            Ok(manager) => Some(Rc::new(manager)),
+            Err(e) => {
+                println!("Global hotkeys are not available: {}", e);
+                None
+            }
+        };
+
+        // The OS delivers hotkeys on its own thread, the window may be asleep, so wake it up
+        let (sender, pressed) = mpsc::channel();
+        let ctx = ctx.clone();
+        GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+            if event.state == HotKeyState::Pressed && sender.send(event.id).is_ok() {
+                ctx.request_repaint();
+            }
+        }));
+
+        // register('Ctrl+Shift+Space', handler)
+        let manager_clone = manager.clone();
+        let registered_clone = registered.clone();
+        let register = isolate.create_function(move |invocation| {
+            let (keys, handler): (String, Function) = invocation.args.into(&invocation.mv8)?;
+            let manager = manager_clone
+                .as_ref()
+                .ok_or_else(|| external_error("Global hotkeys are not available".to_string()))?;
+            let hotkey: HotKey = keys
+                .parse()
+                .map_err(|e| external_error(format!("Invalid hotkey {}: {}", keys, e)))?;
+            let mut registered = registered_clone.borrow_mut();
+            if let Some(existing) = registered.get(&hotkey.id()) {
+                return Err(external_error(format!(
+                    "Hotkey {} conflicts with the registered {}",
+                    keys, existing.keys
+                )));
+            }
+            // fails when another application already owns the hotkey
+            manager
+                .register(hotkey)
+                .map_err(|e| external_error(format!("Failed to register {}: {}", keys, e)))?;
+            registered.insert(
+                hotkey.id(),
+                RegisteredHotkey {
+                    keys,
+                    hotkey,
+                    handler,
+                },
+            );
+            Ok(())
+        });
+        object
+            .set("register", register)
+            .expect("Failed to set globalHotkeys.register");
+
+        // unregister('Ctrl+Shift+Space') -> whether the hotkey was registered
+        let manager_clone = manager.clone();
+        let registered_clone = registered.clone();
+        let unregister = isolate.create_function(move |invocation| {
+            let (keys,): (String,) = invocation.args.into(&invocation.mv8)?;
+            let hotkey: HotKey = keys
+                .parse()
+                .map_err(|e| external_error(format!("Invalid hotkey {}: {}", keys, e)))?;
+            let Some(registered_hotkey) = registered_clone.borrow_mut().remove(&hotkey.id()) else {
+                return Ok(false);
+            };
+            if let Some(manager) = &manager_clone {
+                manager
+                    .unregister(registered_hotkey.hotkey)
+                    .map_err(|e| external_error(format!("Failed to unregister {}: {}", keys, e)))?;
+            }
+            Ok(true)
+        });
+        object
+            .set("unregister", unregister)
+            .expect("Failed to set globalHotkeys.unregister");
+
+        Self {
+            object,
+            registered,
+            pressed,
+        }
+    }
+
+    pub fn object(&self) -> Object {
+        self.object.clone()
+    }
+
+    pub fn run(&self, isolate: &MiniV8, ctx: &egui::Context) {
+        for id in self.pressed.try_iter() {
+            let triggered = self.registered.borrow().get(&id).map(|registered_hotkey| {
+                (
+                    registered_hotkey.keys.clone(),
+                    registered_hotkey.handler.clone(),
+                )
+            });
+            if let Some((keys, handler)) = triggered {
+                let event = EventBuilder::new(isolate, "hotkey", 0)
+                    .input(ctx)
+                    .field("keys", keys)
+                    .build();
+                call_handler(isolate, &handler, "hotkey", vec![event]);
+            }
+        }
+    }
+}
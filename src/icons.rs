@@ -0,0 +1,76 @@
+use eframe::egui;
+use egui_phosphor::regular as phosphor;
+
+// Icon names accepted by the `icon` prop, mapped to glyphs of the bundled Phosphor font
+const ICONS: &[(&str, &str)] = &[
+    ("add", phosphor::PLUS),
+    ("arrow-down", phosphor::ARROW_DOWN),
+    ("arrow-left", phosphor::ARROW_LEFT),
+    ("arrow-right", phosphor::ARROW_RIGHT),
+    ("arrow-up", phosphor::ARROW_UP),
+    ("bell", phosphor::BELL),
+    ("calendar", phosphor::CALENDAR),
+    ("camera", phosphor::CAMERA),
+    ("chart", phosphor::CHART_LINE),
+    ("check", phosphor::CHECK),
+    ("clock", phosphor::CLOCK),
+    ("close", phosphor::X),
+    ("copy", phosphor::COPY),
+    ("cut", phosphor::SCISSORS),
+    ("delete", phosphor::TRASH),
+    ("download", phosphor::DOWNLOAD_SIMPLE),
+    ("edit", phosphor::PENCIL_SIMPLE),
+    ("error", phosphor::X_CIRCLE),
+    ("eye", phosphor::EYE),
+    ("eye-off", phosphor::EYE_SLASH),
+    ("file", phosphor::FILE),
+    ("folder", phosphor::FOLDER),
+    ("heart", phosphor::HEART),
+    ("home", phosphor::HOUSE),
+    ("image", phosphor::IMAGE),
+    ("info", phosphor::INFO),
+    ("link", phosphor::LINK),
+    ("lock", phosphor::LOCK),
+    ("mail", phosphor::ENVELOPE),
+    ("menu", phosphor::LIST),
+    ("open", phosphor::FOLDER_OPEN),
+    ("paste", phosphor::CLIPBOARD),
+    ("pause", phosphor::PAUSE),
+    ("play", phosphor::PLAY),
+    ("redo", phosphor::ARROW_CLOCKWISE),
+    ("refresh", phosphor::ARROWS_CLOCKWISE),
+    ("remove", phosphor::MINUS),
+    ("save", phosphor::FLOPPY_DISK),
+    ("search", phosphor::MAGNIFYING_GLASS),
+    ("settings", phosphor::GEAR),
+    ("star", phosphor::STAR),
+    ("stop", phosphor::STOP),
+    ("undo", phosphor::ARROW_COUNTER_CLOCKWISE),
+    ("unlock", phosphor::LOCK_OPEN),
+    ("upload", phosphor::UPLOAD_SIMPLE),
+    ("user", phosphor::USER),
+    ("warning", phosphor::WARNING),
+];
+
+// Adds the icon font as a fallback to the default fonts, so glyphs render in any text
+pub fn register_font(ctx: &egui::Context) {
+    let mut fonts = egui::FontDefinitions::default();
+    egui_phosphor::add_to_fonts(&mut fonts, egui_phosphor::Variant::Regular);
+    ctx.set_fonts(fonts);
+}
+
+fn glyph(name: &str) -> Option<&'static str> {
+    ICONS
+        .iter()
+        .find(|(icon, _)| *icon == name)
+        .map(|(_, glyph)| *glyph)
+}
+
+// Prepends the named icon's glyph to a label, unknown names leave the text as is
+pub fn prepend(icon: Option<&str>, text: &str) -> String {
+    match icon.and_then(glyph) {
+        Some(glyph) if text.is_empty() => glyph.to_string(),
+        Some(glyph) => format!("{} {}", glyph, text),
+        None => text.to_string(),
+    }
+}
@@ -1,15 +1,62 @@
 use std::{
-    cell::{Ref, RefCell},
+    cell::{Cell, Ref, RefCell},
     collections::{HashMap, HashSet},
+    path::Path,
     rc::Rc,
 };
 
-use color_eyre::owo_colors::OwoColorize;
 use eframe::egui::{self, Response};
 use eyre::{Context, ContextCompat, Result};
-use mini_v8::{Error as MiniV8Error, Function, MiniV8, ToValue, Value, Variadic};
+use mini_v8::{Error as MiniV8Error, Function, MiniV8, Object, ToValue, Value};
 
-use colored::*;
+mod animation;
+mod base64;
+mod bundle;
+mod canvas;
+mod color;
+mod console_table;
+mod custom_elements;
+mod env;
+mod event;
+mod fetch;
+mod global_hotkeys;
+mod icons;
+mod markup;
+mod modules;
+mod profiler;
+mod selector;
+mod shortcuts;
+mod storage;
+mod stores;
+mod styles;
+mod svg;
+mod text_encoding;
+mod textures;
+mod theme;
+mod timers;
+mod tree;
+mod url;
+mod viewport;
+mod window;
+use animation::{Animation, Phase};
+use bundle::{pinia_source, VueBundle};
+use canvas::CanvasContext;
+use custom_elements::{CustomElements, UiCommand};
+use event::{call_handler, report_error, report_unhandled_rejection, EventBuilder};
+use fetch::Fetch;
+use global_hotkeys::GlobalHotkeys;
+use modules::FileLoader;
+use profiler::Profiler;
+use shortcuts::Shortcuts;
+use storage::WebStorage;
+use stores::Store;
+use styles::Styles;
+use svg::SvgCache;
+use textures::{TextureRegistry, TextureStatus};
+use theme::Theme;
+use timers::{AnimationFrames, Timers};
+use viewport::{Camera, TrianglePlugin, ViewportRegistry};
+use window::WindowEvents;
 
 fn value_to_string(
     isolate: &MiniV8,
@@ -22,23 +69,23 @@ fn value_to_string(
     let next_indent = "  ".repeat(depth + 1);
 
     if value.is_null() {
-        return Ok(format!("{}", "null".bold()));
+        return Ok("null".to_string());
     }
 
     if value.is_boolean() {
         let bool_value: bool = value.into(isolate).expect("Failed to convert boolean");
-        return Ok(format!("{}", bool_value.to_string().yellow()));
+        return Ok(bool_value.to_string());
     }
 
     if value.is_number() {
         let number_value: f64 = value.into(isolate).expect("Failed to convert number");
-        return Ok(format!("{}", number_value.to_string().yellow()));
+        return Ok(number_value.to_string());
     }
 
     if value.is_string() {
         if member {
             let string_value: String = value.into(isolate).expect("Failed to convert string");
-            return Ok(format!("\"{}\"", string_value).green().to_string());
+            return Ok(format!("\"{}\"", string_value));
         } else {
             let string_value: String = value.into(isolate).expect("Failed to convert string");
             return Ok(format!("{}", string_value));
@@ -53,16 +100,13 @@ fn value_to_string(
         } else {
             function_name
         };
-        return Ok(format!("[Function: {}]", function_name)
-            .bold()
-            .cyan()
-            .to_string());
+        return Ok(format!("[Function: {}]", function_name));
     }
 
     if value.is_array() {
         let value_hash = value.hash(isolate);
         if seen.contains(&value_hash) {
-            return Ok("[Circular]".bold().red().to_string());
+            return Ok("[Circular]".to_string());
         }
         seen.insert(value_hash);
 
@@ -103,7 +147,7 @@ fn value_to_string(
     } else if value.is_object() {
         let value_hash = value.hash(isolate);
         if seen.contains(&value_hash) {
-            return Ok("[Circular]".bold().red().to_string());
+            return Ok("[Circular]".to_string());
         }
         seen.insert(value_hash);
 
@@ -122,7 +166,7 @@ fn value_to_string(
 
             let key_string: String = key.into(isolate).expect("Failed to convert key");
             let value_string = value_to_string(isolate, value, depth + 1, seen, true)?;
-            entries.push(format!("{}: {}", key_string.blue(), value_string));
+            entries.push(format!("{}: {}", key_string, value_string));
         }
 
         if length <= 3 && entries.iter().map(|s| s.len()).sum::<usize>() <= 60 {
@@ -151,17 +195,368 @@ fn value_to_string(
     }
 }
 
+// Emits a console call as a tracing event, the formatted arguments as the message and the console
+// method as a field so the subscriber decides on colors. Events use the `console` target, so
+// `RUST_LOG=console=warn` silences the script's logs below warnings
+fn log_console(method: &str, args: &[String]) {
+    let message = args.join(" ");
+    match method {
+        "error" => tracing::error!(target: "console", method, "{}", message),
+        "warn" => tracing::warn!(target: "console", method, "{}", message),
+        "debug" => tracing::debug!(target: "console", method, "{}", message),
+        _ => tracing::info!(target: "console", method, "{}", message),
+    }
+}
+
+// The own properties of an array or object as (key, value) pairs, `None` for anything else
+fn value_entries(isolate: &MiniV8, value: &Value) -> Result<Option<Vec<(String, Value)>>> {
+    let object = match value {
+        Value::Array(array) => array.clone().into_object(),
+        Value::Object(object) => object.clone(),
+        _ => return Ok(None),
+    };
+    let keys = object.keys(false).wrap_err("Failed to get keys")?;
+    let mut entries = Vec::new();
+    for i in 0..keys.len() {
+        let key: Value = keys.get(i).wrap_err("Failed to get key")?;
+        let value: Value = object.get(key.clone()).wrap_err("Failed to get value")?;
+        let key = key
+            .coerce_string(isolate)
+            .wrap_err("Failed to convert key")?;
+        entries.push((key, value));
+    }
+    Ok(Some(entries))
+}
+
+// Formats `console.table(data, columns)`, one row per entry of `data` and a column per property
+// of the rows (or the given `columns`), rows that aren't objects go into a Values column.
+// `None` when `data` isn't an array or object
+fn value_to_table(
+    isolate: &MiniV8,
+    data: &Value,
+    columns: Option<Vec<String>>,
+) -> Result<Option<String>> {
+    let Some(entries) = value_entries(isolate, data)? else {
+        return Ok(None);
+    };
+    let format = |value: Value| value_to_string(isolate, value, 1, &mut HashSet::new(), true);
+
+    let mut rows = Vec::new();
+    let mut keys: Vec<String> = Vec::new();
+    let mut has_values = false;
+    for (index, value) in entries {
+        let properties = match value {
+            Value::Function(_) => None,
+            ref value => value_entries(isolate, value)?,
+        };
+        match properties {
+            Some(properties) => {
+                for (key, _) in &properties {
+                    if !keys.contains(key) {
+                        keys.push(key.clone());
+                    }
+                }
+                rows.push((
+                    index,
+                    properties.into_iter().collect::<HashMap<_, _>>(),
+                    None,
+                ));
+            }
+            None => {
+                has_values = true;
+                rows.push((index, HashMap::new(), Some(value)));
+            }
+        }
+    }
+
+    let keys = columns.unwrap_or(keys);
+    let mut headers = vec!["(index)".to_string()];
+    headers.extend(keys.iter().cloned());
+    if has_values {
+        headers.push("Values".to_string());
+    }
+    let mut cells = Vec::new();
+    for (index, mut properties, value) in rows {
+        let mut row = vec![index];
+        for key in &keys {
+            row.push(match properties.remove(key) {
+                Some(value) => format(value)?,
+                None => String::new(),
+            });
+        }
+        if let Some(value) = value {
+            row.push(format(value)?);
+        }
+        cells.push(row);
+    }
+    Ok(Some(console_table::render(&headers, &cells)))
+}
+
+// Converts an array of column titles or `{ title, width }` objects into table columns
+fn value_to_table_columns(isolate: &MiniV8, value: &Value) -> Result<Vec<TableColumn>> {
+    let mut columns = Vec::new();
+    if let Some(array) = value.as_array() {
+        for i in 0..array.len() {
+            let column: Value = array.get(i).wrap_err("Failed to get column")?;
+            if let Some(object) = column.as_object() {
+                let title: String = object.get("title").wrap_err("Failed to get title")?;
+                let width: Option<f64> = object.get("width").wrap_err("Failed to get width")?;
+                columns.push(TableColumn {
+                    title,
+                    width: width.map(|width| width as f32),
+                });
+            } else {
+                let title = column
+                    .coerce_string(isolate)
+                    .wrap_err("Failed to convert title")?;
+                columns.push(TableColumn {
+                    title: title.to_string(),
+                    width: None,
+                });
+            }
+        }
+    }
+    Ok(columns)
+}
+
+// Converts an array of strings or `{ label, value }` objects into segmented control options
+fn value_to_segmented_options(isolate: &MiniV8, value: &Value) -> Result<Vec<SegmentedOption>> {
+    let mut options = Vec::new();
+    if let Some(array) = value.as_array() {
+        for i in 0..array.len() {
+            let option: Value = array.get(i).wrap_err("Failed to get option")?;
+            if let Some(object) = option.as_object() {
+                let value: Value = object.get("value").wrap_err("Failed to get value")?;
+                let value = value
+                    .coerce_string(isolate)
+                    .wrap_err("Failed to convert value")?
+                    .to_string();
+                let label: Option<String> = object.get("label").wrap_err("Failed to get label")?;
+                options.push(SegmentedOption {
+                    label: label.unwrap_or_else(|| value.clone()),
+                    value,
+                });
+            } else {
+                let value = option
+                    .coerce_string(isolate)
+                    .wrap_err("Failed to convert option")?
+                    .to_string();
+                options.push(SegmentedOption {
+                    label: value.clone(),
+                    value,
+                });
+            }
+        }
+    }
+    Ok(options)
+}
+
+// Converts an array of `[x, y]` pairs or plain numbers (x being the index) into plot points
+fn value_to_plot_points(isolate: &MiniV8, value: &Value) -> Result<Vec<[f64; 2]>> {
+    let mut points = Vec::new();
+    if let Some(array) = value.as_array() {
+        for i in 0..array.len() {
+            let point: Value = array.get(i).wrap_err("Failed to get point")?;
+            if let Some(point) = point.as_array() {
+                let x: f64 = point.get(0).wrap_err("Failed to get x")?;
+                let y: f64 = point.get(1).wrap_err("Failed to get y")?;
+                points.push([x, y]);
+            } else {
+                let y = point
+                    .coerce_number(isolate)
+                    .wrap_err("Failed to convert point")?;
+                points.push([i as f64, y]);
+            }
+        }
+    }
+    Ok(points)
+}
+
+// Converts an array of `{ name, points, color }` objects into plot series
+fn value_to_plot_series(isolate: &MiniV8, value: &Value) -> Result<Vec<PlotSeries>> {
+    let mut series = Vec::new();
+    if let Some(array) = value.as_array() {
+        for i in 0..array.len() {
+            let object: Object = array.get(i).wrap_err("Failed to get series")?;
+            let name: Option<String> = object.get("name").wrap_err("Failed to get name")?;
+            let points: Value = object.get("points").wrap_err("Failed to get points")?;
+            let color: Value = object.get("color").wrap_err("Failed to get color")?;
+            let color = if color.is_undefined() || color.is_null() {
+                None
+            } else {
+                Some(value_to_color32(isolate, &color)?)
+            };
+            series.push(PlotSeries {
+                name,
+                points: value_to_plot_points(isolate, &points)?,
+                color,
+            });
+        }
+    }
+    Ok(series)
+}
+
+// Converts an `[r, g, b]` or `[r, g, b, a]` array (0-255) or a CSS color string into a color
+fn value_to_color32(isolate: &MiniV8, value: &Value) -> Result<egui::Color32> {
+    if let Some(color) = value.as_string() {
+        return color::parse_css_color(&color.to_string()).map_err(|e| eyre::eyre!(e));
+    }
+    let array = value.as_array().wrap_err("Expected a color array")?;
+    let component = |index: u32| -> Result<u8> {
+        if index >= array.len() {
+            return Ok(255);
+        }
+        let component: Value = array.get(index).wrap_err("Failed to get color component")?;
+        let component = component
+            .coerce_number(isolate)
+            .wrap_err("Failed to convert color component")?;
+        Ok(component.clamp(0.0, 255.0) as u8)
+    };
+    Ok(egui::Color32::from_rgba_unmultiplied(
+        component(0)?,
+        component(1)?,
+        component(2)?,
+        component(3)?,
+    ))
+}
+
+// Converts a number prop, where `null`/`undefined` unsets it
+fn value_to_optional_number(isolate: &MiniV8, value: &Value) -> Result<Option<f64>> {
+    if value.is_undefined() || value.is_null() {
+        Ok(None)
+    } else {
+        let value = value
+            .coerce_number(isolate)
+            .wrap_err("Failed to convert number")?;
+        Ok(Some(value))
+    }
+}
+
+// Converts a number or a `[x, y]` array into a vector, e.g. for spacing props
+fn value_to_vec2(isolate: &MiniV8, value: &Value) -> Result<egui::Vec2> {
+    if let Some(array) = value.as_array() {
+        let x: f64 = array.get(0).wrap_err("Failed to get x")?;
+        let y: f64 = array.get(1).wrap_err("Failed to get y")?;
+        Ok(egui::vec2(x as f32, y as f32))
+    } else {
+        let value = value
+            .coerce_number(isolate)
+            .wrap_err("Failed to convert number")?;
+        Ok(egui::Vec2::splat(value as f32))
+    }
+}
+
+// Parses a shortcut like `Ctrl+Shift+S`, `Cmd` is the mac command key and `Mod` is
+// command on mac and control everywhere else
+fn parse_keyboard_shortcut(keys: &str) -> Result<egui::KeyboardShortcut> {
+    let mut modifiers = egui::Modifiers::NONE;
+    let mut key = None;
+    for part in keys.split('+').map(str::trim) {
+        match part.to_lowercase().as_str() {
+            "ctrl" | "control" => modifiers = modifiers | egui::Modifiers::CTRL,
+            "shift" => modifiers = modifiers | egui::Modifiers::SHIFT,
+            "alt" | "option" => modifiers = modifiers | egui::Modifiers::ALT,
+            "cmd" | "command" | "meta" => modifiers = modifiers | egui::Modifiers::MAC_CMD,
+            "mod" => modifiers = modifiers | egui::Modifiers::COMMAND,
+            _ => {
+                let name = if part.chars().count() == 1 {
+                    part.to_uppercase()
+                } else {
+                    part.to_string()
+                };
+                key = Some(
+                    egui::Key::from_name(&name)
+                        .wrap_err_with(|| format!("Unknown key in shortcut: {}", part))?,
+                );
+            }
+        }
+    }
+    let key = key.wrap_err_with(|| format!("Missing key in shortcut: {}", keys))?;
+    Ok(egui::KeyboardShortcut::new(modifiers, key))
+}
+
+// Shows the tooltip once the widget has been hovered for `delay` seconds
+fn show_delayed_tooltip(response: &Response, element_id: ElementId, tooltip: &str, delay: f32) {
+    let ctx = &response.ctx;
+    let hover_id = egui::Id::new(("tooltip_hover", element_id));
+    if !response.hovered() {
+        ctx.data_mut(|data| data.remove::<f64>(hover_id));
+        return;
+    }
+    let now = ctx.input(|i| i.time);
+    let hovered_since = ctx.data_mut(|data| *data.get_temp_mut_or_insert_with(hover_id, || now));
+    let remaining = delay as f64 - (now - hovered_since);
+    if remaining > 0.0 {
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(remaining));
+    } else {
+        egui::show_tooltip_at_pointer(ctx, hover_id, |ui| ui.label(tooltip));
+    }
+}
+
+// Maps CSS cursor names onto egui cursors
+fn parse_cursor_icon(cursor: &str) -> Result<egui::CursorIcon> {
+    use egui::CursorIcon;
+    Ok(match cursor {
+        "default" | "auto" => CursorIcon::Default,
+        "none" => CursorIcon::None,
+        "pointer" => CursorIcon::PointingHand,
+        "text" => CursorIcon::Text,
+        "vertical-text" => CursorIcon::VerticalText,
+        "grab" => CursorIcon::Grab,
+        "grabbing" => CursorIcon::Grabbing,
+        "move" => CursorIcon::Move,
+        "not-allowed" => CursorIcon::NotAllowed,
+        "no-drop" => CursorIcon::NoDrop,
+        "wait" => CursorIcon::Wait,
+        "progress" => CursorIcon::Progress,
+        "crosshair" => CursorIcon::Crosshair,
+        "help" => CursorIcon::Help,
+        "cell" => CursorIcon::Cell,
+        "copy" => CursorIcon::Copy,
+        "alias" => CursorIcon::Alias,
+        "context-menu" => CursorIcon::ContextMenu,
+        "all-scroll" => CursorIcon::AllScroll,
+        "zoom-in" => CursorIcon::ZoomIn,
+        "zoom-out" => CursorIcon::ZoomOut,
+        "ew-resize" => CursorIcon::ResizeHorizontal,
+        "ns-resize" => CursorIcon::ResizeVertical,
+        "nwse-resize" => CursorIcon::ResizeNwSe,
+        "nesw-resize" => CursorIcon::ResizeNeSw,
+        "col-resize" => CursorIcon::ResizeColumn,
+        "row-resize" => CursorIcon::ResizeRow,
+        _ => eyre::bail!("Unknown cursor: {}", cursor),
+    })
+}
+
+// The window title, also names the directory the app's data is kept in
+const APP_NAME: &str = "vgui demo";
+
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
+    // `RUST_LOG` filters the logs (`info` by default), an embedder's own subscriber takes precedence
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .try_init();
 
-    let native_options = eframe::NativeOptions::default();
+    let bundle = VueBundle::from_env();
+    println!("Using Vue bundle: {:?}", bundle);
+    let entry = modules::entry_from_env();
+
+    // The 3d viewports paint with wgpu callbacks, so Glow (eframe's default) won't do
+    let native_options = eframe::NativeOptions {
+        renderer: eframe::Renderer::Wgpu,
+        ..Default::default()
+    };
     eframe::run_native(
-        "vgui demo",
+        APP_NAME,
         native_options,
-        Box::new(|cc| {
+        Box::new(move |cc| {
             Box::new(
-                GuiApp::new(cc)
+                GuiApp::new(cc, &bundle, entry.as_deref())
                     .wrap_err("Failed to create app")
                     .expect("Failed to create app"),
             )
@@ -180,19 +575,395 @@ enum Element {
     Comment(String),
     Label(String),
     Button(String),
-    Vertical,
-    Horizontal,
+    Vertical(LayoutOptions),
+    Horizontal(LayoutOptions),
     Separator,
-    TextEdit(String),
+    TextEdit {
+        text: String,
+        password: bool,
+        revealable: bool,
+        placeholder: String,
+    },
+    Checkbox(String, bool),
+    Slider {
+        text: String,
+        value: f64,
+        min: f64,
+        max: f64,
+        step: Option<f64>,
+        format: NumberFormat,
+    },
+    RadioGroup(String),
+    Radio {
+        text: String,
+        value: String,
+    },
+    Progress {
+        value: f32,
+        text: Option<String>,
+    },
+    Image(String),
+    Link {
+        text: String,
+        href: String,
+    },
+    Collapsing {
+        title: String,
+        default_open: bool,
+    },
+    Window {
+        title: String,
+        open: bool,
+        resizable: bool,
+        position: Option<egui::Pos2>,
+    },
+    Grid {
+        columns: Option<usize>,
+        spacing: Option<egui::Vec2>,
+        striped: bool,
+    },
+    Row,
+    Tabs(usize),
+    Tab(String),
+    Table {
+        columns: Vec<TableColumn>,
+        striped: bool,
+        resizable: bool,
+    },
+    TextArea {
+        text: String,
+        rows: usize,
+        desired_width: Option<f32>,
+        placeholder: String,
+        auto_grow: bool,
+    },
+    Number {
+        value: f64,
+        min: Option<f64>,
+        max: Option<f64>,
+        speed: f64,
+        format: NumberFormat,
+    },
+    Spinner(Option<f32>),
+    MenuBar,
+    Menu(String),
+    MenuItem(String),
+    ContextMenu,
+    Plot {
+        series: Vec<PlotSeries>,
+        height: Option<f32>,
+    },
+    Canvas(egui::Vec2),
+    Frame {
+        fill: Option<egui::Color32>,
+        stroke_color: Option<egui::Color32>,
+        stroke_width: Option<f32>,
+        rounding: Option<f32>,
+        inner_margin: Option<f32>,
+        outer_margin: Option<f32>,
+        spacing: Option<egui::Vec2>,
+    },
+    Panel {
+        side: PanelSide,
+        resizable: bool,
+    },
+    Markdown(String),
+    Spacer {
+        width: Option<f32>,
+        height: Option<f32>,
+        grow: bool,
+    },
+    Split {
+        ratio: f32,
+        vertical: bool,
+    },
+    Svg {
+        src: String,
+        width: Option<f32>,
+        height: Option<f32>,
+    },
+    Gauge {
+        value: f64,
+        min: f64,
+        max: f64,
+        size: f32,
+        full: bool,
+        text: String,
+    },
+    Segmented {
+        selected: String,
+        options: Vec<SegmentedOption>,
+    },
+    DropZone,
+    Wrap,
+    Scroll {
+        horizontal: bool,
+        vertical: bool,
+        max_height: Option<f32>,
+        offset: egui::Vec2,
+    },
+    Columns(Option<usize>),
+    Area {
+        position: egui::Pos2,
+        draggable: bool,
+    },
+    SelectableLabel {
+        text: String,
+        selection: String,
+    },
+    Span(TextSpan, String),
+    ImageButton {
+        src: String,
+        width: Option<f32>,
+        height: Option<f32>,
+        selected: bool,
+    },
+    Shortcut(Option<egui::KeyboardShortcut>),
+    Sparkline {
+        points: Vec<[f64; 2]>,
+        size: egui::Vec2,
+        color: Option<egui::Color32>,
+    },
+    Viewport3d {
+        renderer: String,
+        size: egui::Vec2,
+        camera: Camera,
+    },
+    // a tag registered with `vgui.registerElement`
+    Custom(String),
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PanelSide {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+#[derive(Debug, Clone)]
+struct TableColumn {
+    title: String,
+    width: Option<f32>,
+}
+// Display options shared by the slider and number elements
+#[derive(Debug, Clone, Default)]
+struct NumberFormat {
+    precision: Option<usize>,
+    prefix: String,
+    suffix: String,
+    logarithmic: bool,
+}
+// Layout of a vertical/horizontal container's children, `align` is the cross axis
+// (or `stretch` to fill it) and `justify` the main axis
+#[derive(Debug, Clone, Copy)]
+struct LayoutOptions {
+    align: Option<egui::Align>,
+    stretch: bool,
+    justify: egui::Align,
+    spacing: Option<egui::Vec2>,
+    padding: Option<f32>,
+}
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        Self {
+            align: None,
+            stretch: false,
+            justify: egui::Align::Min,
+            spacing: None,
+            padding: None,
+        }
+    }
+}
+// Formatting of inline spans (`<b>`, `<i>`, `<color>`) nested inside labels
+#[derive(Debug, Clone, Copy, Default)]
+struct TextSpan {
+    bold: bool,
+    italic: bool,
+    color: Option<egui::Color32>,
+}
+impl TextSpan {
+    // Inner spans add to the formatting of the outer ones
+    fn merge(self, inner: TextSpan) -> TextSpan {
+        TextSpan {
+            bold: self.bold || inner.bold,
+            italic: self.italic || inner.italic,
+            color: inner.color.or(self.color),
+        }
+    }
+
+    fn rich_text(&self, text: &str) -> egui::RichText {
+        let mut rich_text = egui::RichText::new(text);
+        if self.bold {
+            rich_text = rich_text.strong();
+        }
+        if self.italic {
+            rich_text = rich_text.italics();
+        }
+        if let Some(color) = self.color {
+            rich_text = rich_text.color(color);
+        }
+        rich_text
+    }
+}
+#[derive(Debug, Clone)]
+struct SegmentedOption {
+    label: String,
+    value: String,
+}
+#[derive(Debug, Clone)]
+struct PlotSeries {
+    name: Option<String>,
+    points: Vec<[f64; 2]>,
+    color: Option<egui::Color32>,
 }
 struct Events {
     click: Option<Function>,
-    hover: Option<Function>,
+    mouse_enter: Option<Function>,
+    mouse_leave: Option<Function>,
     input: Option<Function>,
+    update_open: Option<Function>,
+    draw: Option<Function>,
+    drop: Option<Function>,
+    select: Option<Function>,
+    trigger: Option<Function>,
+    camera: Option<Function>,
+    change: Option<Function>,
+    commit: Option<Function>,
+    key_down: Option<Function>,
+    key_up: Option<Function>,
+    focus: Option<Function>,
+    blur: Option<Function>,
+    shown: Option<Function>,
+    hidden: Option<Function>,
+    scroll: Option<Function>,
+    submit: Option<Function>,
+    wheel: Option<Function>,
+    drag_start: Option<Function>,
+    drag: Option<Function>,
+    drag_end: Option<Function>,
+    paste: Option<Function>,
+    composition_start: Option<Function>,
+    composition_update: Option<Function>,
+    composition_end: Option<Function>,
+    update_model_value: Option<Function>,
+}
+// Raw input collected once per frame, for elements that need more than their widget's response
+#[derive(Default)]
+struct FrameInput {
+    hovered_files: usize,
+    dropped_files: Vec<String>,
+    key_events: Vec<KeyEvent>,
+    composition_events: Vec<CompositionEvent>,
+    // Whether an IME composition is in progress, kept across frames
+    composing: bool,
+}
+#[derive(Debug, Clone, Copy)]
+struct KeyEvent {
+    key: egui::Key,
+    pressed: bool,
+    modifiers: egui::Modifiers,
+}
+// IME composition, in the order of the DOM's compositionstart/update/end events
+#[derive(Debug, Clone)]
+enum CompositionEvent {
+    Start,
+    Update(String),
+    End(String),
+}
+// The elements rendered in the last frame and in the current one
+#[derive(Default)]
+struct RenderedElements {
+    previous: HashSet<ElementId>,
+    current: HashSet<ElementId>,
+}
+// Props shared by all element types
+#[derive(Default, Clone)]
+struct Props {
+    // the tag the element was created with, for querySelector
+    tag: String,
+    tooltip: Option<String>,
+    tooltip_delay: Option<f32>,
+    disabled: bool,
+    hidden: bool,
+    draggable: bool,
+    sizing: Sizing,
+    classes: Vec<String>,
+    // the `data-v-…` ids of the components whose scoped styles apply
+    scope_ids: Vec<String>,
+    text_wrap: TextWrap,
+    icon: Option<String>,
+    cursor: Option<egui::CursorIcon>,
+    attributes: HashMap<String, Value>,
+}
+// Wrapping of label and button text from the `wrap`, `truncate` and `maxLines` props
+#[derive(Debug, Clone, Copy, Default)]
+struct TextWrap {
+    wrap: Option<bool>,
+    truncate: bool,
+    max_lines: Option<usize>,
+}
+impl TextWrap {
+    // Limits the rows of the text, returns the text and whether the widget should wrap
+    fn apply(
+        &self,
+        ui: &egui::Ui,
+        text: impl Into<egui::WidgetText>,
+        text_style: egui::TextStyle,
+    ) -> (egui::WidgetText, Option<bool>) {
+        let max_rows = if self.truncate {
+            Some(1)
+        } else {
+            self.max_lines
+        };
+        let Some(max_rows) = max_rows else {
+            return (text.into(), self.wrap);
+        };
+        let mut job =
+            text.into()
+                .into_layout_job(ui.style(), text_style.into(), egui::Align::Center);
+        job.wrap.max_rows = max_rows;
+        job.wrap.break_anywhere = self.truncate;
+        (job.into(), Some(true))
+    }
+}
+// Size constraints from the `width`, `height`, `minWidth` and `maxWidth` props
+#[derive(Debug, Clone, Copy, Default)]
+struct Sizing {
+    width: Option<f32>,
+    height: Option<f32>,
+    min_width: Option<f32>,
+    max_width: Option<f32>,
+}
+impl Sizing {
+    // Constrains the ui the element is rendered into, which sizes containers
+    fn apply(&self, ui: &mut egui::Ui) {
+        if let Some(max_width) = self.max_width {
+            ui.set_max_width(max_width);
+        }
+        if let Some(min_width) = self.min_width {
+            ui.set_min_width(min_width);
+        }
+        if let Some(width) = self.width {
+            ui.set_width(width);
+        }
+        if let Some(height) = self.height {
+            ui.set_height(height);
+        }
+    }
+
+    // Widgets that don't stretch on their own (buttons) need an explicit minimum size
+    fn min_size(&self) -> egui::Vec2 {
+        egui::vec2(
+            self.width.or(self.min_width).unwrap_or(0.0),
+            self.height.unwrap_or(0.0),
+        )
+    }
 }
 
 type ElementRef = Rc<RefCell<Element>>;
 type ElementId = usize;
+// Drag-and-drop payload, the id of the element being dragged
+#[derive(Debug, Clone, Copy)]
+struct DragSource(ElementId);
 type Elements = HashMap<ElementId, ElementRef>;
 type ElementsRef = Rc<RefCell<Elements>>;
 type ElementsVec = Vec<ElementId>;
@@ -200,12 +971,42 @@ type ElementsChildren = HashMap<ElementId, ElementsVec>;
 type ElementsChildrenRef = Rc<RefCell<ElementsChildren>>;
 type ElementEvents = HashMap<ElementId, Events>;
 type ElementEventsRef = Rc<RefCell<ElementEvents>>;
+type ElementProps = HashMap<ElementId, Props>;
+type ElementPropsRef = Rc<RefCell<ElementProps>>;
 
 struct GuiApp {
     isolate: MiniV8,
     elements: ElementsRef,
     elements_children: ElementsChildrenRef,
     element_events: ElementEventsRef,
+    element_props: ElementPropsRef,
+    textures: TextureRegistry,
+    canvas: CanvasContext,
+    markdown_cache: RefCell<egui_commonmark::CommonMarkCache>,
+    svg_cache: RefCell<SvgCache>,
+    frame_input: RefCell<FrameInput>,
+    rendered_elements: RefCell<RenderedElements>,
+    hovered_elements: RefCell<HashSet<ElementId>>,
+    drag_totals: RefCell<HashMap<ElementId, egui::Vec2>>,
+    viewports: ViewportRegistry,
+    theme: Theme,
+    styles: Styles,
+    window: WindowEvents,
+    shortcuts: Shortcuts,
+    global_hotkeys: GlobalHotkeys,
+    focus_requests: Rc<RefCell<HashSet<ElementId>>>,
+    animations: Rc<RefCell<HashMap<ElementId, Animation>>>,
+    tooltip_delay: Rc<Cell<Option<f32>>>,
+    tree_changed: Rc<Cell<bool>>,
+    custom_elements: Rc<CustomElements>,
+    // the store field bound to egui's zoom factor (see `bind_zoom`), the zoom it asked for and
+    // the one last synced with it
+    zoom_binding: RefCell<Option<(Store, String)>>,
+    requested_zoom: Rc<Cell<Option<f32>>>,
+    zoom: Cell<f32>,
+    timers: Timers,
+    animation_frames: AnimationFrames,
+    fetch: Fetch,
 }
 
 macro_rules! define_js_function {
@@ -231,49 +1032,146 @@ macro_rules! define_js_function {
 }
 
 impl GuiApp {
-    fn new(cc: &eframe::CreationContext<'_>) -> Result<Self> {
+    fn new(
+        cc: &eframe::CreationContext<'_>,
+        bundle: &VueBundle,
+        entry: Option<&Path>,
+    ) -> Result<Self> {
         // initialize JS
         let isolate = MiniV8::new();
         isolate.set_microtasks_policy(v8::MicrotasksPolicy::Explicit);
-        // hook up the console functions (log, warn, error, info)
-        let rust_log_isolate = isolate.clone();
-        let rust_log = isolate.create_function(move |invocation| {
-            let args = invocation.args;
-            let args: Vec<String> = args
-                .iter()
-                .map(|arg| {
-                    value_to_string(
-                        &rust_log_isolate,
-                        arg.clone(),
-                        0,
-                        &mut HashSet::new(),
-                        false,
-                    )
-                    .expect("Failed to convert")
-                })
-                .collect();
-            let args = args.join(" ");
-            println!("{}", args);
+        // hook up the console functions, each logs at its own level (see `log_console`)
+        let console_obj = isolate.create_object();
+        for method in ["log", "info", "debug", "warn", "error"] {
+            let rust_log_isolate = isolate.clone();
+            let rust_log = isolate.create_function(move |invocation| {
+                let args = invocation.args;
+                let args: Vec<String> = args
+                    .iter()
+                    .map(|arg| {
+                        value_to_string(
+                            &rust_log_isolate,
+                            arg.clone(),
+                            0,
+                            &mut HashSet::new(),
+                            false,
+                        )
+                        .expect("Failed to convert")
+                    })
+                    .collect();
+                log_console(method, &args);
+                Ok(())
+            });
+            console_obj
+                .set(method, rust_log)
+                .unwrap_or_else(|_| panic!("Failed to set {}", method));
+        }
+        // console.table(data, columns), data that isn't tabular is logged as is
+        let rust_table_isolate = isolate.clone();
+        let rust_log: Function = console_obj.get("log").expect("Failed to get log");
+        let rust_table = isolate.create_function(move |invocation| {
+            let (data, columns): (Value, Option<Vec<String>>) =
+                invocation.args.into(&invocation.mv8)?;
+            let table = value_to_table(&rust_table_isolate, &data, columns)
+                .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?;
+            match table {
+                Some(table) => log_console("table", &[table]),
+                None => rust_log.call::<_, ()>((data,))?,
+            }
             Ok(())
         });
-        let console_obj = isolate.create_object();
-        console_obj
-            .set("log", rust_log.clone())
-            .expect("Failed to set log");
-        console_obj
-            .set("warn", rust_log.clone())
-            .expect("Failed to set warn");
         console_obj
-            .set("error", rust_log.clone())
-            .expect("Failed to set error");
-        console_obj
-            .set("info", rust_log.clone())
-            .expect("Failed to set info");
+            .set("table", rust_table)
+            .expect("Failed to set table");
         isolate
             .global()
             .set("console", console_obj)
             .expect("Failed to set console");
 
+        // performance, its clock also times the animation frames
+        let profiler = Profiler::new(&isolate);
+
+        // Timers (setTimeout, setInterval, clearTimeout, clearInterval) and animation frames
+        let timers = Timers::new(&isolate);
+        let animation_frames = AnimationFrames::new(&isolate, profiler.origin());
+
+        // fetch() with Headers and Response, TextEncoder and TextDecoder, URL and URLSearchParams
+        let fetch = Fetch::new(&isolate, &cc.egui_ctx);
+        text_encoding::register(&isolate);
+        url::register(&isolate);
+
+        // localStorage persists under the platform data dir, sessionStorage lives in memory
+        let local_storage_path =
+            storage::data_dir(APP_NAME).map(|dir| dir.join("localStorage.json"));
+        isolate
+            .global()
+            .set(
+                "localStorage",
+                WebStorage::local(&isolate, local_storage_path).object(),
+            )
+            .expect("Failed to set localStorage");
+        isolate
+            .global()
+            .set("sessionStorage", WebStorage::session(&isolate).object())
+            .expect("Failed to set sessionStorage");
+
+        // App-level APIs live on the `vgui` global
+        let vgui_obj = isolate.create_object();
+        let theme = Theme::new(&isolate);
+        vgui_obj
+            .set("theme", theme.object())
+            .expect("Failed to set theme");
+        let styles = Styles::new(&isolate);
+        vgui_obj
+            .set("styles", styles.object())
+            .expect("Failed to set styles");
+        let shortcuts = Shortcuts::new(&isolate);
+        vgui_obj
+            .set("shortcuts", shortcuts.object())
+            .expect("Failed to set shortcuts");
+        let global_hotkeys = GlobalHotkeys::new(&isolate, &cc.egui_ctx);
+        vgui_obj
+            .set("globalHotkeys", global_hotkeys.object())
+            .expect("Failed to set globalHotkeys");
+        let window = WindowEvents::new(&isolate);
+        vgui_obj
+            .set("window", window.object())
+            .expect("Failed to set window");
+        // setTooltipDelay(ms), `null` restores egui's default tooltip timing
+        let tooltip_delay: Rc<Cell<Option<f32>>> = Rc::new(Cell::new(None));
+        let tooltip_delay_clone = tooltip_delay.clone();
+        let set_tooltip_delay = isolate.create_function(move |invocation| {
+            let (delay,): (Option<f64>,) = invocation.args.into(&invocation.mv8)?;
+            tooltip_delay_clone.set(delay.map(|delay| delay as f32 / 1000.0));
+            Ok(())
+        });
+        vgui_obj
+            .set("setTooltipDelay", set_tooltip_delay)
+            .expect("Failed to set setTooltipDelay");
+        let custom_elements = Rc::new(CustomElements::new(&isolate));
+        vgui_obj
+            .set("registerElement", custom_elements.register_function())
+            .expect("Failed to set registerElement");
+        // base64.encode/decode work on bytes, this also sets the Latin-1 `atob` and `btoa`
+        vgui_obj
+            .set("base64", base64::register(&isolate))
+            .expect("Failed to set base64");
+        // Read-only environment variables, narrowed with `--env-allow` or `VGUI_ENV_ALLOW`
+        vgui_obj
+            .set("env", env::object(&isolate))
+            .expect("Failed to set env");
+        // The app module's path, the demo app is only mounted without one
+        vgui_obj
+            .set(
+                "entry",
+                entry.map(|entry| entry.to_string_lossy().into_owned()),
+            )
+            .expect("Failed to set entry");
+        isolate
+            .global()
+            .set("vgui", vgui_obj)
+            .expect("Failed to set vgui");
+
         // Virtual DOM CRUD
         let elements = Rc::new(RefCell::new(HashMap::new()));
         elements
@@ -282,11 +1180,25 @@ impl GuiApp {
         let elements_children: Rc<RefCell<HashMap<usize, Vec<usize>>>> =
             Rc::new(RefCell::new(HashMap::new()));
         let element_events = Rc::new(RefCell::new(HashMap::new()));
+        let element_props: ElementPropsRef = Rc::new(RefCell::new(HashMap::new()));
+        element_props.borrow_mut().insert(
+            0,
+            Props {
+                tag: "root".to_string(),
+                ..Default::default()
+            },
+        );
+        // Set by every node op that changes the tree, so changes made outside of input handling
+        // (resolved promises, async components) still get a frame to show up in
+        let tree_changed = Rc::new(Cell::new(false));
 
         // Create element (createElement)
         let elements_clone = elements.clone();
+        let element_props_clone = element_props.clone();
+        let custom_elements_clone = custom_elements.clone();
         define_js_function!(isolate, "createElement", 2, |id: ElementId, tag: String| {
             println!("Creating element: {}", tag);
+            element_props_clone.borrow_mut().entry(id).or_default().tag = tag.clone();
             match tag.as_str() {
                 "label" => {
                     elements_clone
@@ -294,14 +1206,16 @@ impl GuiApp {
                         .insert(id, Rc::new(RefCell::new(Element::Label("".to_string()))));
                 }
                 "vertical" => {
-                    elements_clone
-                        .borrow_mut()
-                        .insert(id, Rc::new(RefCell::new(Element::Vertical)));
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Vertical(Default::default()))),
+                    );
                 }
                 "horizontal" => {
-                    elements_clone
-                        .borrow_mut()
-                        .insert(id, Rc::new(RefCell::new(Element::Horizontal)));
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Horizontal(Default::default()))),
+                    );
                 }
                 "button" => {
                     elements_clone
@@ -324,130 +1238,515 @@ impl GuiApp {
                         .insert(id, Rc::new(RefCell::new(Element::Separator)));
                 }
                 "text-edit" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::TextEdit {
+                            text: "".to_string(),
+                            password: false,
+                            revealable: false,
+                            placeholder: "".to_string(),
+                        })),
+                    );
+                }
+                "checkbox" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Checkbox("".to_string(), false))),
+                    );
+                }
+                "slider" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Slider {
+                            text: "".to_string(),
+                            value: 0.0,
+                            min: 0.0,
+                            max: 100.0,
+                            step: None,
+                            format: NumberFormat::default(),
+                        })),
+                    );
+                }
+                "radio-group" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::RadioGroup("".to_string()))),
+                    );
+                }
+                "radio" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Radio {
+                            text: "".to_string(),
+                            value: "".to_string(),
+                        })),
+                    );
+                }
+                "progress" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Progress {
+                            value: 0.0,
+                            text: None,
+                        })),
+                    );
+                }
+                "image" => {
                     elements_clone
                         .borrow_mut()
-                        .insert(id, Rc::new(RefCell::new(Element::TextEdit("".to_string()))));
+                        .insert(id, Rc::new(RefCell::new(Element::Image("".to_string()))));
                 }
-                _ => {
-                    return Err(MiniV8Error::ExternalError(
-                        format!("Unknown tag: {}", tag).into(),
-                    ));
+                "image-button" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::ImageButton {
+                            src: "".to_string(),
+                            width: None,
+                            height: None,
+                            selected: false,
+                        })),
+                    );
                 }
-            }
-            Ok(id)
-        });
-
-        // Insert element (insertElement)
-        let rust_node_ops_isolate = isolate.clone();
-        let elements_clone = elements.clone();
-        let elements_children_clone = elements_children.clone();
-        let rust_insert = isolate.create_function(move |invocation| {
-            let args = invocation.args;
-            if args.len() != 3 {
-                return Err(MiniV8Error::ExternalError("Expected 3 arguments".into()));
-            }
-            let child = args.get(0);
-            let parent = args.get(1);
-            let anchor = args.get(2);
-            let child: ElementId = child
-                .into(&rust_node_ops_isolate)
-                .expect("Failed to convert child");
-            let parent: ElementId = parent
-                .into(&rust_node_ops_isolate)
-                .expect("Failed to convert parent");
-            let anchor: Option<ElementId> = anchor
-                .into(&rust_node_ops_isolate)
-                .expect("Failed to convert anchor");
-
-            let elements_borrow = elements_clone.borrow();
-            let child_element = elements_borrow.get(&child).expect("Failed to get child");
-            let parent_element = elements_borrow.get(&parent).expect("Failed to get parent");
-            let anchor_element = anchor.map(|id| elements_borrow.get(&id)).flatten();
-
-            println!("---------------------");
-            println!("Inserting element: {} - {:?}", child, child_element);
-            println!("++ Parent: {} - {:?}", parent, parent_element);
-            println!("++ Anchor: {:?} - {:?}", anchor, anchor_element);
-            println!("---------------------");
-
-            // Ensure the child is not already inserted elsewhere
-            let mut elements_children_borrow = elements_children_clone.borrow_mut();
-            for (parent_id, children) in elements_children_borrow.iter_mut() {
-                if children.contains(&child) {
-                    panic!(
-                        "Child element {} is already a child of parent element {}",
-                        child, parent_id
+                "shortcut" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Shortcut(None))));
+                }
+                "sparkline" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Sparkline {
+                            points: Vec::new(),
+                            size: egui::vec2(80.0, 16.0),
+                            color: None,
+                        })),
                     );
                 }
-            }
-
-            let parent_children = elements_children_borrow
-                .entry(parent)
-                .or_insert_with(Vec::new);
-            let anchor_index = anchor
-                .map(|anchor| {
-                    parent_children
-                        .iter()
-                        .position(|id| id == &anchor)
-                        .expect("Failed to get anchor index")
-                })
-                .unwrap_or(parent_children.len());
-            parent_children.insert(anchor_index, child);
-
-            Ok(())
-        });
-        isolate
-            .global()
-            .set("insertElement", rust_insert)
-            .expect("Failed to set insert");
-
-        // Remove element (removeElement)
-        let rust_node_ops_isolate = isolate.clone();
-        let elements_clone = elements.clone();
-        let elements_children_clone = elements_children.clone();
-        let rust_remove = isolate.create_function(move |invocation| {
-            let args = invocation.args;
-            if args.len() != 1 {
-                return Err(MiniV8Error::ExternalError("Expected 1 argument".into()));
-            }
-            let child = args.get(0);
-            let child: ElementId = child
-                .into(&rust_node_ops_isolate)
-                .expect("Failed to convert child");
-
-            // find parent
-            let mut parent = None;
-            let mut elements_children_borrow = elements_children_clone.borrow_mut();
-            for (parent_id, children) in elements_children_borrow.iter_mut() {
-                if let Some(index) = children.iter().position(|id| id == &child) {
-                    parent = Some(*parent_id);
-                    children.remove(index);
-                    break;
+                "viewport3d" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Viewport3d {
+                            renderer: "".to_string(),
+                            size: egui::vec2(320.0, 240.0),
+                            camera: Camera::default(),
+                        })),
+                    );
                 }
-            }
-
-            let elements_borrow = elements_clone.borrow();
-            let child_element = elements_borrow.get(&child).expect("Failed to get child");
-            let parent_element = parent
-                .map(|id| elements_borrow.get(&id))
-                .flatten()
-                .expect("Failed to get parent");
-
-            println!("---------------------");
-            println!("Removing element: {} - {:?}", child, child_element);
-            println!("++ Parent: {:?} - {:?}", parent, parent_element);
-            println!("---------------------");
-
-            Ok(())
-        });
-        isolate
-            .global()
-            .set("removeElement", rust_remove)
-            .expect("Failed to set removeElement");
-
+                "link" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Link {
+                            text: "".to_string(),
+                            href: "".to_string(),
+                        })),
+                    );
+                }
+                "collapsing" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Collapsing {
+                            title: "".to_string(),
+                            default_open: false,
+                        })),
+                    );
+                }
+                "window" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Window {
+                            title: "".to_string(),
+                            open: true,
+                            resizable: true,
+                            position: None,
+                        })),
+                    );
+                }
+                "grid" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Grid {
+                            columns: None,
+                            spacing: None,
+                            striped: false,
+                        })),
+                    );
+                }
+                "row" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Row)));
+                }
+                "tabs" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Tabs(0))));
+                }
+                "tab" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Tab("".to_string()))));
+                }
+                "table" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Table {
+                            columns: Vec::new(),
+                            striped: false,
+                            resizable: false,
+                        })),
+                    );
+                }
+                "text-area" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::TextArea {
+                            text: "".to_string(),
+                            rows: 4,
+                            desired_width: None,
+                            placeholder: "".to_string(),
+                            auto_grow: false,
+                        })),
+                    );
+                }
+                "number" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Number {
+                            value: 0.0,
+                            min: None,
+                            max: None,
+                            speed: 1.0,
+                            format: NumberFormat::default(),
+                        })),
+                    );
+                }
+                "spinner" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Spinner(None))));
+                }
+                "menu-bar" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::MenuBar)));
+                }
+                "menu" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Menu("".to_string()))));
+                }
+                "menu-item" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::MenuItem("".to_string()))));
+                }
+                "context-menu" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::ContextMenu)));
+                }
+                "plot" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Plot {
+                            series: Vec::new(),
+                            height: None,
+                        })),
+                    );
+                }
+                "canvas" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Canvas(egui::vec2(200.0, 200.0)))),
+                    );
+                }
+                "frame" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Frame {
+                            fill: None,
+                            stroke_color: None,
+                            stroke_width: None,
+                            rounding: None,
+                            inner_margin: None,
+                            outer_margin: None,
+                            spacing: None,
+                        })),
+                    );
+                }
+                "side-panel" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Panel {
+                            side: PanelSide::Left,
+                            resizable: true,
+                        })),
+                    );
+                }
+                "top-panel" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Panel {
+                            side: PanelSide::Top,
+                            resizable: false,
+                        })),
+                    );
+                }
+                "bottom-panel" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Panel {
+                            side: PanelSide::Bottom,
+                            resizable: false,
+                        })),
+                    );
+                }
+                "markdown" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Markdown("".to_string()))));
+                }
+                "spacer" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Spacer {
+                            width: None,
+                            height: None,
+                            grow: false,
+                        })),
+                    );
+                }
+                "split" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Split {
+                            ratio: 0.5,
+                            vertical: false,
+                        })),
+                    );
+                }
+                "svg" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Svg {
+                            src: "".to_string(),
+                            width: None,
+                            height: None,
+                        })),
+                    );
+                }
+                "gauge" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Gauge {
+                            value: 0.0,
+                            min: 0.0,
+                            max: 100.0,
+                            size: 80.0,
+                            full: false,
+                            text: "".to_string(),
+                        })),
+                    );
+                }
+                "segmented" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Segmented {
+                            selected: "".to_string(),
+                            options: Vec::new(),
+                        })),
+                    );
+                }
+                "drop-zone" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::DropZone)));
+                }
+                "wrap" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Wrap)));
+                }
+                "scroll" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Scroll {
+                            horizontal: false,
+                            vertical: true,
+                            max_height: None,
+                            offset: egui::Vec2::ZERO,
+                        })),
+                    );
+                }
+                "columns" => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Columns(None))));
+                }
+                "area" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Area {
+                            position: egui::Pos2::ZERO,
+                            draggable: false,
+                        })),
+                    );
+                }
+                "selectable-label" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::SelectableLabel {
+                            text: "".to_string(),
+                            selection: "".to_string(),
+                        })),
+                    );
+                }
+                "b" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Span(
+                            TextSpan {
+                                bold: true,
+                                ..Default::default()
+                            },
+                            "".to_string(),
+                        ))),
+                    );
+                }
+                "i" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Span(
+                            TextSpan {
+                                italic: true,
+                                ..Default::default()
+                            },
+                            "".to_string(),
+                        ))),
+                    );
+                }
+                "color" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Span(
+                            Default::default(),
+                            "".to_string(),
+                        ))),
+                    );
+                }
+                // <Suspense> keeps its pending branch in an off-screen `div`, it's never
+                // inserted into the tree, so any container does
+                "div" => {
+                    elements_clone.borrow_mut().insert(
+                        id,
+                        Rc::new(RefCell::new(Element::Vertical(Default::default()))),
+                    );
+                }
+                _ if custom_elements_clone.is_registered(&tag) => {
+                    elements_clone
+                        .borrow_mut()
+                        .insert(id, Rc::new(RefCell::new(Element::Custom(tag.clone()))));
+                }
+                _ => {
+                    return Err(MiniV8Error::ExternalError(
+                        format!("Unknown tag: {}", tag).into(),
+                    ));
+                }
+            }
+            Ok(id)
+        });
+
+        // Insert element (insertElement)
+        let rust_node_ops_isolate = isolate.clone();
+        let elements_clone = elements.clone();
+        let elements_children_clone = elements_children.clone();
+        let tree_changed_clone = tree_changed.clone();
+        let rust_insert = isolate.create_function(move |invocation| {
+            let args = invocation.args;
+            if args.len() != 3 {
+                return Err(MiniV8Error::ExternalError("Expected 3 arguments".into()));
+            }
+            let child = args.get(0);
+            let parent = args.get(1);
+            let anchor = args.get(2);
+            let child: ElementId = child
+                .into(&rust_node_ops_isolate)
+                .expect("Failed to convert child");
+            let parent: ElementId = parent
+                .into(&rust_node_ops_isolate)
+                .expect("Failed to convert parent");
+            let anchor: Option<ElementId> = anchor
+                .into(&rust_node_ops_isolate)
+                .expect("Failed to convert anchor");
+
+            let elements_borrow = elements_clone.borrow();
+            let child_element = elements_borrow.get(&child).expect("Failed to get child");
+            let parent_element = elements_borrow.get(&parent).expect("Failed to get parent");
+            let anchor_element = anchor.map(|id| elements_borrow.get(&id)).flatten();
+
+            println!("---------------------");
+            println!("Inserting element: {} - {:?}", child, child_element);
+            println!("++ Parent: {} - {:?}", parent, parent_element);
+            println!("++ Anchor: {:?} - {:?}", anchor, anchor_element);
+            println!("---------------------");
+
+            // Inserting an attached child moves it, Vue relies on that for keyed moves and for
+            // <Suspense> swapping its resolved branch in
+            tree::insert(
+                &mut elements_children_clone.borrow_mut(),
+                child,
+                parent,
+                anchor,
+            )
+            .map_err(|e| MiniV8Error::ExternalError(e.into()))?;
+            tree_changed_clone.set(true);
+
+            Ok(())
+        });
+        isolate
+            .global()
+            .set("insertElement", rust_insert)
+            .expect("Failed to set insert");
+
+        // Remove element (removeElement)
+        let rust_node_ops_isolate = isolate.clone();
+        let elements_clone = elements.clone();
+        let elements_children_clone = elements_children.clone();
+        let tree_changed_clone = tree_changed.clone();
+        let rust_remove = isolate.create_function(move |invocation| {
+            let args = invocation.args;
+            if args.len() != 1 {
+                return Err(MiniV8Error::ExternalError("Expected 1 argument".into()));
+            }
+            let child = args.get(0);
+            let child: ElementId = child
+                .into(&rust_node_ops_isolate)
+                .expect("Failed to convert child");
+
+            // removing a detached element does nothing, like in the DOM
+            let parent = tree::detach(&mut elements_children_clone.borrow_mut(), child);
+            tree_changed_clone.set(true);
+
+            let elements_borrow = elements_clone.borrow();
+            let child_element = elements_borrow.get(&child).expect("Failed to get child");
+            let parent_element = parent.and_then(|id| elements_borrow.get(&id));
+
+            println!("---------------------");
+            println!("Removing element: {} - {:?}", child, child_element);
+            println!("++ Parent: {:?} - {:?}", parent, parent_element);
+            println!("---------------------");
+
+            Ok(())
+        });
+        isolate
+            .global()
+            .set("removeElement", rust_remove)
+            .expect("Failed to set removeElement");
+
         // Set element text (setElementText)
         let rust_node_ops_isolate = isolate.clone();
         let elements_clone = elements.clone();
+        let tree_changed_clone = tree_changed.clone();
         let rust_set_element_text = isolate.create_function(move |invocation| {
             let args = invocation.args;
             if args.len() != 2 {
@@ -461,6 +1760,7 @@ impl GuiApp {
             let text: String = text
                 .into(&rust_node_ops_isolate)
                 .expect("Failed to convert text");
+            tree_changed_clone.set(true);
 
             let elements_borrow = elements_clone.borrow();
             let element_ref = elements_borrow
@@ -487,7 +1787,37 @@ impl GuiApp {
                 Element::Comment(comment) => {
                     *comment = text.clone();
                 }
-                Element::TextEdit(label) => {
+                Element::TextEdit { text: label, .. } => {
+                    *label = text.clone();
+                }
+                Element::Checkbox(label, _) => {
+                    *label = text.clone();
+                }
+                Element::Slider { text: label, .. } => {
+                    *label = text.clone();
+                }
+                Element::Radio { text: label, .. } => {
+                    *label = text.clone();
+                }
+                Element::Link { text: label, .. } => {
+                    *label = text.clone();
+                }
+                Element::TextArea { text: label, .. } => {
+                    *label = text.clone();
+                }
+                Element::MenuItem(label) => {
+                    *label = text.clone();
+                }
+                Element::Markdown(source) => {
+                    *source = text.clone();
+                }
+                Element::Gauge { text: label, .. } => {
+                    *label = text.clone();
+                }
+                Element::SelectableLabel { text: label, .. } => {
+                    *label = text.clone();
+                }
+                Element::Span(_, label) => {
                     *label = text.clone();
                 }
                 _ => {
@@ -526,16 +1856,10 @@ impl GuiApp {
             println!("Getting parent node of: {} - {:?}", node, node_element);
             println!("---------------------");
 
-            let children_borrow = element_children_clone.borrow();
-            for (parent, children) in children_borrow.iter() {
-                if children.contains(&node) {
-                    return Ok((*parent)
-                        .to_value(&rust_node_ops_isolate)
-                        .expect("Failed to convert"));
-                }
-            }
-
-            Ok(Value::Null)
+            let parent = tree::parent(&element_children_clone.borrow(), node);
+            Ok(parent
+                .to_value(&rust_node_ops_isolate)
+                .expect("Failed to convert"))
         });
         isolate
             .global()
@@ -563,17 +1887,9 @@ impl GuiApp {
             println!("---------------------");
             println!("Getting next sibling of: {} - {:?}", node, node_element);
 
-            let children_borrow = element_children_clone.borrow();
-            let mut sibling = Value::Null;
-            for (_, children) in children_borrow.iter() {
-                if let Some(index) = children.iter().position(|id| id == &node) {
-                    if index < children.len() - 1 {
-                        sibling = (children[index + 1])
-                            .to_value(&rust_node_ops_isolate)
-                            .expect("Failed to convert");
-                    }
-                }
-            }
+            let sibling = tree::next_sibling(&element_children_clone.borrow(), node)
+                .to_value(&rust_node_ops_isolate)
+                .expect("Failed to convert");
 
             println!("Next sibling: {:?}", sibling);
             println!("---------------------");
@@ -585,15 +1901,192 @@ impl GuiApp {
             .set("nextSibling", rust_next_sibling)
             .expect("Failed to set nextSibling");
 
+        // Clone element (cloneElement), copies an element with its props but without its event
+        // handlers, like the DOM's cloneNode. The copies get consecutive ids from `first_id` in
+        // tree order, which are returned so the script can wrap them
+        let elements_clone = elements.clone();
+        let elements_children_clone = elements_children.clone();
+        let element_props_clone = element_props.clone();
+        let tree_changed_clone = tree_changed.clone();
+        define_js_function!(
+            isolate,
+            "cloneElement",
+            3,
+            |id: ElementId, first_id: ElementId, deep: bool| {
+                let mut elements = elements_clone.borrow_mut();
+                let mut children = elements_children_clone.borrow_mut();
+                let mut element_props = element_props_clone.borrow_mut();
+                let mut ids = Vec::new();
+                // (source, parent of the copy)
+                let mut stack = vec![(id, None)];
+                while let Some((source, parent)) = stack.pop() {
+                    let element = elements
+                        .get(&source)
+                        .ok_or_else(|| {
+                            MiniV8Error::ExternalError(
+                                format!("Unknown element: {}", source).into(),
+                            )
+                        })?
+                        .borrow()
+                        .clone();
+                    let copy = first_id + ids.len();
+                    ids.push(copy);
+                    elements.insert(copy, Rc::new(RefCell::new(element)));
+                    if let Some(props) = element_props.get(&source).cloned() {
+                        element_props.insert(copy, props);
+                    }
+                    if let Some(parent) = parent {
+                        children.entry(parent).or_default().push(copy);
+                    }
+                    if deep {
+                        let source_children = children.get(&source).cloned().unwrap_or_default();
+                        stack.extend(
+                            source_children
+                                .into_iter()
+                                .rev()
+                                .map(|child| (child, Some(copy))),
+                        );
+                    }
+                }
+                tree_changed_clone.set(true);
+                Ok(ids)
+            }
+        );
+
+        // Is registered element (isRegisteredElement), lets the compiler treat them as elements
+        let custom_elements_clone = custom_elements.clone();
+        define_js_function!(isolate, "isRegisteredElement", 1, |tag: String| {
+            Ok(custom_elements_clone.is_registered(&tag))
+        });
+
+        // Parse static content (parseStaticContent), the markup of hoisted static template chunks
+        define_js_function!(isolate, "parseStaticContent", 1, |content: String| {
+            markup::parse(&content).map_err(|e| MiniV8Error::ExternalError(e.into()))
+        });
+
+        // Query selector (querySelector), the first match in tree order, the root included
+        let elements_children_clone = elements_children.clone();
+        let element_props_clone = element_props.clone();
+        let query_isolate = isolate.clone();
+        define_js_function!(isolate, "querySelector", 1, |selector: String| {
+            let selectors =
+                selector::parse(&selector).map_err(|e| MiniV8Error::ExternalError(e.into()))?;
+            let children = elements_children_clone.borrow();
+            let element_props = element_props_clone.borrow();
+            let mut stack = vec![0];
+            while let Some(id) = stack.pop() {
+                if let Some(props) = element_props.get(&id) {
+                    // attributes whose toString throws make querySelector throw
+                    let attributes = props
+                        .attributes
+                        .iter()
+                        .map(|(name, value)| {
+                            let value = value.coerce_string(&query_isolate)?.to_string();
+                            Ok((name.as_str(), value))
+                        })
+                        .collect::<Result<HashMap<_, _>, MiniV8Error>>()?;
+                    let attribute = |name: &str| attributes.get(name).cloned();
+                    if selectors
+                        .iter()
+                        .any(|selector| selector.matches(&props.tag, attribute))
+                    {
+                        return Ok(Some(id));
+                    }
+                }
+                if let Some(element_children) = children.get(&id) {
+                    stack.extend(element_children.iter().rev());
+                }
+            }
+            Ok(None)
+        });
+
+        // Focus element (focusElement), the focus is requested when the element is rendered next
+        let focus_requests: Rc<RefCell<HashSet<ElementId>>> = Rc::new(RefCell::new(HashSet::new()));
+        let focus_requests_clone = focus_requests.clone();
+        define_js_function!(isolate, "focusElement", 1, |id: ElementId| {
+            focus_requests_clone.borrow_mut().insert(id);
+            Ok(())
+        });
+
+        // Animate element (animateElement), runs an enter or leave transition and calls `done`
+        // once it finished
+        let animations: Rc<RefCell<HashMap<ElementId, Animation>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let animations_clone = animations.clone();
+        define_js_function!(
+            isolate,
+            "animateElement",
+            6,
+            |id: ElementId,
+             phase: String,
+             duration: f64,
+             offset_x: f64,
+             offset_y: f64,
+             done: Function| {
+                let phase = match phase.as_str() {
+                    "enter" => Phase::Enter,
+                    "leave" => Phase::Leave,
+                    _ => {
+                        return Err(MiniV8Error::ExternalError(
+                            format!("Unknown animation phase: {}", phase).into(),
+                        ))
+                    }
+                };
+                let mut animations = animations_clone.borrow_mut();
+                let animation = Animation::new(
+                    phase,
+                    std::time::Duration::from_secs_f64(duration.max(0.0) / 1000.0),
+                    egui::vec2(offset_x as f32, offset_y as f32),
+                    done,
+                    animations.get(&id),
+                );
+                animations.insert(id, animation);
+                Ok(())
+            }
+        );
+
+        // Set scope id (setScopeId), an element gets the scope id of its component, and of the
+        // components whose slots it's rendered from
+        let element_props_clone = element_props.clone();
+        define_js_function!(
+            isolate,
+            "setScopeId",
+            2,
+            |id: ElementId, scope_id: String| {
+                let mut element_props = element_props_clone.borrow_mut();
+                let scope_ids = &mut element_props.entry(id).or_default().scope_ids;
+                if !scope_ids.contains(&scope_id) {
+                    scope_ids.push(scope_id);
+                }
+                Ok(())
+            }
+        );
+
+        // Get attribute (getAttribute), props that aren't handled by any element are stored as is
+        let element_props_clone = element_props.clone();
+        define_js_function!(isolate, "getAttribute", 2, |id: ElementId, key: String| {
+            let element_props_borrow = element_props_clone.borrow();
+            let attribute = element_props_borrow
+                .get(&id)
+                .and_then(|props: &Props| props.attributes.get(&key))
+                .cloned()
+                .unwrap_or(Value::Null);
+            Ok(attribute)
+        });
+
         // Property patching (patchProp)
         let rust_node_ops_isolate = isolate.clone();
         let elements_clone = elements.clone();
         let elements_events_clone = element_events.clone();
+        let element_props_clone = element_props.clone();
+        let focus_requests_clone = focus_requests.clone();
+        let tree_changed_clone = tree_changed.clone();
         let rust_patch_prop = isolate.create_function(move |invocation| {
             let args = invocation.args;
             if args.len() != 4 {
                 return Err(MiniV8Error::ExternalError("Expected 4 arguments".into()));
             }
+            tree_changed_clone.set(true);
             let element = args.get(0);
             let key = args.get(1);
             let prev_value = args.get(2);
@@ -616,7 +2109,7 @@ impl GuiApp {
                 .get(&element)
                 .expect("Failed to get element");
 
-            let element_mut = element_ref.borrow_mut();
+            let mut element_mut = element_ref.borrow_mut();
             println!("---------------------");
             println!(
                 "Patching prop: {} from {:?} to {:?}",
@@ -625,339 +2118,3722 @@ impl GuiApp {
             println!("Element: {} - {:?}", element, element_mut);
             println!("---------------------");
 
-            // Check for events (onClick, onHover)
-            let mut events_borrow = elements_events_clone.borrow_mut();
-            // create the events object if it doesn't exist
-            let events = events_borrow.entry(element).or_insert_with(|| Events {
-                click: None,
-                hover: None,
-                input: None,
-            });
-            // now add or remove the event
-            match key.as_str() {
-                "onClick" => {
-                    if next_value.is_function() {
-                        events.click = Some(next_value.as_function().unwrap().clone());
-                    } else {
-                        events.click = None;
-                    }
+            // Element specific props (checked, min, max, ...)
+            let mut element_prop = true;
+            match (&mut *element_mut, key.as_str()) {
+                (Element::TextEdit { password, .. }, "password") => {
+                    *password = next_value.coerce_boolean(&rust_node_ops_isolate);
                 }
-                "onHover" => {
-                    if next_value.is_function() {
-                        events.hover = Some(next_value.as_function().unwrap().clone());
-                    } else {
-                        events.hover = None;
-                    }
+                (Element::TextEdit { revealable, .. }, "revealable") => {
+                    *revealable = next_value.coerce_boolean(&rust_node_ops_isolate);
                 }
-                "onInput" => {
-                    if next_value.is_function() {
-                        events.input = Some(next_value.as_function().unwrap().clone());
-                    } else {
-                        events.input = None;
-                    }
+                (
+                    Element::TextEdit { text, .. } | Element::TextArea { text, .. },
+                    "value" | "modelValue",
+                ) => {
+                    *text = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert value")
+                        .to_string();
+                }
+                (Element::Checkbox(_, checked), "checked" | "modelValue") => {
+                    *checked = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Slider { value, .. }, "value" | "modelValue") => {
+                    *value = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert value");
+                }
+                (Element::Slider { min, .. }, "min") => {
+                    *min = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert min");
+                }
+                (Element::Slider { max, .. }, "max") => {
+                    *max = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert max");
+                }
+                (Element::Slider { step, .. }, "step") => {
+                    *step = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            next_value
+                                .coerce_number(&rust_node_ops_isolate)
+                                .expect("Failed to convert step"),
+                        )
+                    };
+                }
+                (Element::RadioGroup(selected), "value" | "modelValue") => {
+                    *selected = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert value")
+                        .to_string();
+                }
+                (Element::Radio { value, .. }, "value") => {
+                    *value = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert value")
+                        .to_string();
+                }
+                (Element::Progress { value, .. }, "value") => {
+                    let progress = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert value");
+                    *value = progress.clamp(0.0, 1.0) as f32;
+                }
+                (Element::Progress { text, .. }, "text") => {
+                    *text = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            next_value
+                                .coerce_string(&rust_node_ops_isolate)
+                                .expect("Failed to convert text")
+                                .to_string(),
+                        )
+                    };
+                }
+                (Element::Image(src), "src") => {
+                    *src = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert src")
+                        .to_string();
+                }
+                (Element::ImageButton { src, .. }, "src") => {
+                    *src = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert src")
+                        .to_string();
+                }
+                (Element::ImageButton { width, .. }, "width") => {
+                    *width = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert width")
+                        .map(|width| width as f32);
+                }
+                (Element::ImageButton { height, .. }, "height") => {
+                    *height = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert height")
+                        .map(|height| height as f32);
+                }
+                (Element::ImageButton { selected, .. }, "selected") => {
+                    *selected = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Link { href, .. }, "href") => {
+                    *href = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert href")
+                        .to_string();
+                }
+                (Element::Collapsing { title, .. }, "title") => {
+                    *title = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert title")
+                        .to_string();
+                }
+                (Element::Collapsing { default_open, .. }, "defaultOpen" | "default-open") => {
+                    *default_open = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Window { title, .. }, "title") => {
+                    *title = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert title")
+                        .to_string();
+                }
+                (Element::Window { open, .. }, "open") => {
+                    *open = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Window { resizable, .. }, "resizable") => {
+                    *resizable = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Window { position, .. }, "x") => {
+                    let x = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert x") as f32;
+                    let y = position.map_or(0.0, |position| position.y);
+                    *position = Some(egui::pos2(x, y));
+                }
+                (Element::Window { position, .. }, "y") => {
+                    let y = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert y") as f32;
+                    let x = position.map_or(0.0, |position| position.x);
+                    *position = Some(egui::pos2(x, y));
+                }
+                (Element::Grid { columns, .. }, "columns") => {
+                    *columns = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            next_value
+                                .coerce_number(&rust_node_ops_isolate)
+                                .expect("Failed to convert columns")
+                                as usize,
+                        )
+                    };
+                }
+                (Element::Grid { spacing, .. }, "spacing") => {
+                    *spacing = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            value_to_vec2(&rust_node_ops_isolate, &next_value)
+                                .expect("Failed to convert spacing"),
+                        )
+                    };
+                }
+                (Element::Grid { striped, .. }, "striped") => {
+                    *striped = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Tabs(active), "active" | "modelValue") => {
+                    *active = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert active") as usize;
+                }
+                (Element::Tab(title), "title") => {
+                    *title = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert title")
+                        .to_string();
+                }
+                (Element::Table { columns, .. }, "columns") => {
+                    *columns = value_to_table_columns(&rust_node_ops_isolate, &next_value)
+                        .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?;
+                }
+                (Element::Table { striped, .. }, "striped") => {
+                    *striped = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Table { resizable, .. }, "resizable") => {
+                    *resizable = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (
+                    Element::TextEdit { placeholder, .. } | Element::TextArea { placeholder, .. },
+                    "placeholder",
+                ) => {
+                    *placeholder = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert placeholder")
+                        .to_string();
+                }
+                (Element::TextArea { rows, .. }, "rows") => {
+                    *rows = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert rows") as usize;
+                }
+                (Element::TextArea { auto_grow, .. }, "autoGrow" | "auto-grow") => {
+                    *auto_grow = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::TextArea { desired_width, .. }, "desiredWidth" | "desired-width") => {
+                    *desired_width = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            next_value
+                                .coerce_number(&rust_node_ops_isolate)
+                                .expect("Failed to convert desired width")
+                                as f32,
+                        )
+                    };
+                }
+                (Element::Number { value, .. }, "value" | "modelValue") => {
+                    *value = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert value");
+                }
+                (Element::Number { min, .. }, "min") => {
+                    *min = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert min");
+                }
+                (Element::Number { max, .. }, "max") => {
+                    *max = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert max");
+                }
+                (Element::Number { speed, .. }, "speed") => {
+                    *speed = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert speed");
+                }
+                (Element::Slider { format, .. } | Element::Number { format, .. }, "precision") => {
+                    format.precision =
+                        value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                            .expect("Failed to convert precision")
+                            .map(|precision| precision as usize);
+                }
+                (Element::Slider { format, .. } | Element::Number { format, .. }, "prefix") => {
+                    format.prefix = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert prefix")
+                        .to_string();
+                }
+                (Element::Slider { format, .. } | Element::Number { format, .. }, "suffix") => {
+                    format.suffix = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert suffix")
+                        .to_string();
+                }
+                (
+                    Element::Slider { format, .. } | Element::Number { format, .. },
+                    "logarithmic",
+                ) => {
+                    format.logarithmic = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Menu(title), "title") => {
+                    *title = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert title")
+                        .to_string();
+                }
+                (Element::Plot { series, .. }, "points") => {
+                    *series = vec![PlotSeries {
+                        name: None,
+                        points: value_to_plot_points(&rust_node_ops_isolate, &next_value)
+                            .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?,
+                        color: None,
+                    }];
+                }
+                (Element::Plot { series, .. }, "series") => {
+                    *series = value_to_plot_series(&rust_node_ops_isolate, &next_value)
+                        .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?;
+                }
+                (Element::Plot { height, .. }, "height") => {
+                    *height = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert height")
+                        .map(|height| height as f32);
+                }
+                (Element::Canvas(size), "width") => {
+                    size.x = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert width") as f32;
+                }
+                (Element::Canvas(size), "height") => {
+                    size.y = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert height") as f32;
+                }
+                (Element::Frame { fill, .. }, "fill") => {
+                    *fill = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            value_to_color32(&rust_node_ops_isolate, &next_value).map_err(|e| {
+                                MiniV8Error::ExternalError(format!("{:?}", e).into())
+                            })?,
+                        )
+                    };
+                }
+                (Element::Frame { stroke_color, .. }, "stroke") => {
+                    *stroke_color = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            value_to_color32(&rust_node_ops_isolate, &next_value).map_err(|e| {
+                                MiniV8Error::ExternalError(format!("{:?}", e).into())
+                            })?,
+                        )
+                    };
+                }
+                (Element::Frame { stroke_width, .. }, "strokeWidth" | "stroke-width") => {
+                    *stroke_width = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert stroke width")
+                        .map(|width| width as f32);
+                }
+                (Element::Frame { rounding, .. }, "rounding") => {
+                    *rounding = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert rounding")
+                        .map(|rounding| rounding as f32);
+                }
+                (Element::Frame { inner_margin, .. }, "padding") => {
+                    *inner_margin = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert padding")
+                        .map(|padding| padding as f32);
+                }
+                (Element::Frame { outer_margin, .. }, "margin") => {
+                    *outer_margin = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert margin")
+                        .map(|margin| margin as f32);
+                }
+                (Element::Panel { side, .. }, "side") => {
+                    let value = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert side")
+                        .to_string();
+                    *side = match value.as_str() {
+                        "left" => PanelSide::Left,
+                        "right" => PanelSide::Right,
+                        "top" => PanelSide::Top,
+                        "bottom" => PanelSide::Bottom,
+                        _ => {
+                            return Err(MiniV8Error::ExternalError(
+                                format!("Unknown panel side: {}", value).into(),
+                            ));
+                        }
+                    };
+                }
+                (Element::Panel { resizable, .. }, "resizable") => {
+                    *resizable = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Markdown(source), "source") => {
+                    *source = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert source")
+                        .to_string();
+                }
+                (Element::Spacer { width, .. }, "width") => {
+                    *width = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert width")
+                        .map(|width| width as f32);
+                }
+                (Element::Spacer { height, .. }, "height") => {
+                    *height = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert height")
+                        .map(|height| height as f32);
+                }
+                (Element::Spacer { grow, .. }, "grow") => {
+                    *grow = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Split { ratio, .. }, "ratio") => {
+                    let value = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert ratio");
+                    *ratio = value.clamp(0.0, 1.0) as f32;
+                }
+                (Element::Split { vertical, .. }, "direction") => {
+                    let direction = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert direction")
+                        .to_string();
+                    *vertical = direction == "vertical";
+                }
+                (Element::Svg { src, .. }, "src") => {
+                    *src = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert src")
+                        .to_string();
+                }
+                (Element::Svg { width, .. }, "width") => {
+                    *width = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert width")
+                        .map(|width| width as f32);
+                }
+                (Element::Svg { height, .. }, "height") => {
+                    *height = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert height")
+                        .map(|height| height as f32);
+                }
+                (Element::Gauge { value, .. }, "value") => {
+                    *value = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert value");
+                }
+                (Element::Gauge { min, .. }, "min") => {
+                    *min = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert min");
+                }
+                (Element::Gauge { max, .. }, "max") => {
+                    *max = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert max");
+                }
+                (Element::Gauge { size, .. }, "size") => {
+                    *size = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert size") as f32;
+                }
+                (Element::Gauge { full, .. }, "full") => {
+                    *full = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Segmented { selected, .. }, "value" | "modelValue") => {
+                    *selected = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert value")
+                        .to_string();
+                }
+                (Element::Segmented { options, .. }, "options") => {
+                    *options = value_to_segmented_options(&rust_node_ops_isolate, &next_value)
+                        .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?;
+                }
+                (Element::Scroll { horizontal, .. }, "horizontal") => {
+                    *horizontal = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Scroll { vertical, .. }, "vertical") => {
+                    *vertical = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Scroll { max_height, .. }, "maxHeight" | "max-height") => {
+                    *max_height = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert max height")
+                        .map(|max_height| max_height as f32);
+                }
+                (Element::Columns(count), "count") => {
+                    *count = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert count")
+                        .map(|count| count as usize);
+                }
+                (Element::Span(span, _), "value") => {
+                    span.color = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            value_to_color32(&rust_node_ops_isolate, &next_value).map_err(|e| {
+                                MiniV8Error::ExternalError(format!("{:?}", e).into())
+                            })?,
+                        )
+                    };
+                }
+                (Element::Shortcut(shortcut), "keys") => {
+                    let keys = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert keys")
+                        .to_string();
+                    let keys = parse_keyboard_shortcut(&keys)
+                        .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?;
+                    *shortcut = Some(keys);
+                }
+                (Element::Sparkline { points, .. }, "values") => {
+                    *points = value_to_plot_points(&rust_node_ops_isolate, &next_value)
+                        .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?;
+                }
+                (Element::Sparkline { size, .. }, "width") => {
+                    size.x = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert width") as f32;
+                }
+                (Element::Sparkline { size, .. }, "height") => {
+                    size.y = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert height") as f32;
+                }
+                (Element::Sparkline { color, .. }, "color") => {
+                    *color = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            value_to_color32(&rust_node_ops_isolate, &next_value).map_err(|e| {
+                                MiniV8Error::ExternalError(format!("{:?}", e).into())
+                            })?,
+                        )
+                    };
+                }
+                (Element::Viewport3d { renderer, .. }, "renderer") => {
+                    *renderer = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert renderer")
+                        .to_string();
+                }
+                (Element::Viewport3d { size, .. }, "width") => {
+                    size.x = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert width") as f32;
+                }
+                (Element::Viewport3d { size, .. }, "height") => {
+                    size.y = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert height") as f32;
+                }
+                (
+                    Element::Vertical(layout_options) | Element::Horizontal(layout_options),
+                    "align",
+                ) => {
+                    let align = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert align")
+                        .to_string();
+                    layout_options.stretch = align == "stretch";
+                    layout_options.align = match align.as_str() {
+                        "start" | "left" | "top" => Some(egui::Align::Min),
+                        "center" => Some(egui::Align::Center),
+                        "end" | "right" | "bottom" => Some(egui::Align::Max),
+                        _ => None,
+                    };
+                }
+                (
+                    Element::Vertical(layout_options) | Element::Horizontal(layout_options),
+                    "justify",
+                ) => {
+                    let justify = next_value
+                        .coerce_string(&rust_node_ops_isolate)
+                        .expect("Failed to convert justify")
+                        .to_string();
+                    layout_options.justify = match justify.as_str() {
+                        "center" => egui::Align::Center,
+                        "end" => egui::Align::Max,
+                        _ => egui::Align::Min,
+                    };
+                }
+                (
+                    Element::Vertical(layout_options) | Element::Horizontal(layout_options),
+                    "spacing",
+                ) => {
+                    layout_options.spacing = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            value_to_vec2(&rust_node_ops_isolate, &next_value)
+                                .expect("Failed to convert spacing"),
+                        )
+                    };
+                }
+                (
+                    Element::Vertical(layout_options) | Element::Horizontal(layout_options),
+                    "padding",
+                ) => {
+                    layout_options.padding =
+                        value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                            .expect("Failed to convert padding")
+                            .map(|padding| padding as f32);
+                }
+                (Element::Frame { spacing, .. }, "spacing") => {
+                    *spacing = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            value_to_vec2(&rust_node_ops_isolate, &next_value)
+                                .expect("Failed to convert spacing"),
+                        )
+                    };
+                }
+                (Element::Area { position, .. }, "x") => {
+                    position.x = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert x") as f32;
+                }
+                (Element::Area { position, .. }, "y") => {
+                    position.y = next_value
+                        .coerce_number(&rust_node_ops_isolate)
+                        .expect("Failed to convert y") as f32;
+                }
+                (Element::Area { draggable, .. }, "draggable") => {
+                    *draggable = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                (Element::Spinner(size), "size") => {
+                    *size = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert size")
+                        .map(|size| size as f32);
+                }
+                _ => element_prop = false,
+            }
+
+            // Props shared by all elements (tooltip, tooltipDelay, disabled, visible, sizing, class, wrap, icon,
+            // cursor, autofocus, ...)
+            let mut props_borrow = element_props_clone.borrow_mut();
+            let props = props_borrow.entry(element).or_insert_with(Props::default);
+            match key.as_str() {
+                "tooltip" => {
+                    props.tooltip = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            next_value
+                                .coerce_string(&rust_node_ops_isolate)
+                                .expect("Failed to convert tooltip")
+                                .to_string(),
+                        )
+                    };
+                }
+                "disabled" => {
+                    props.disabled = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                "draggable" => {
+                    props.draggable = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                "tooltipDelay" | "tooltip-delay" => {
+                    props.tooltip_delay =
+                        value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                            .expect("Failed to convert tooltipDelay")
+                            .map(|delay| delay as f32 / 1000.0);
+                }
+                "autofocus" => {
+                    if next_value.coerce_boolean(&rust_node_ops_isolate) {
+                        focus_requests_clone.borrow_mut().insert(element);
+                    }
+                }
+                "cursor" => {
+                    props.cursor = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        let cursor = next_value
+                            .coerce_string(&rust_node_ops_isolate)
+                            .expect("Failed to convert cursor")
+                            .to_string();
+                        let cursor = parse_cursor_icon(&cursor)
+                            .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?;
+                        Some(cursor)
+                    };
+                }
+                "icon" => {
+                    props.icon = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(
+                            next_value
+                                .coerce_string(&rust_node_ops_isolate)
+                                .expect("Failed to convert icon")
+                                .to_string(),
+                        )
+                    };
+                }
+                "wrap" => {
+                    props.text_wrap.wrap = if next_value.is_undefined() || next_value.is_null() {
+                        None
+                    } else {
+                        Some(next_value.coerce_boolean(&rust_node_ops_isolate))
+                    };
+                }
+                "truncate" => {
+                    props.text_wrap.truncate = next_value.coerce_boolean(&rust_node_ops_isolate);
+                }
+                "maxLines" | "max-lines" => {
+                    props.text_wrap.max_lines =
+                        value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                            .expect("Failed to convert maxLines")
+                            .map(|max_lines| max_lines as usize);
+                }
+                "visible" => {
+                    props.hidden = !(next_value.is_undefined()
+                        || next_value.coerce_boolean(&rust_node_ops_isolate));
+                }
+                "class" => {
+                    props.classes = if next_value.is_undefined() || next_value.is_null() {
+                        Vec::new()
+                    } else {
+                        next_value
+                            .coerce_string(&rust_node_ops_isolate)
+                            .expect("Failed to convert class")
+                            .to_string()
+                            .split_whitespace()
+                            .map(str::to_string)
+                            .collect()
+                    };
+                }
+                "width" | "height" | "minWidth" | "min-width" | "maxWidth" | "max-width" => {
+                    let size = value_to_optional_number(&rust_node_ops_isolate, &next_value)
+                        .expect("Failed to convert size")
+                        .map(|size| size as f32);
+                    match key.as_str() {
+                        "width" => props.sizing.width = size,
+                        "height" => props.sizing.height = size,
+                        "minWidth" | "min-width" => props.sizing.min_width = size,
+                        _ => props.sizing.max_width = size,
+                    }
+                }
+                // Props nothing knows about are kept for getAttribute
+                _ if !element_prop && !key.starts_with("on") => {
+                    if next_value.is_undefined() || next_value.is_null() {
+                        props.attributes.remove(&key);
+                    } else {
+                        props.attributes.insert(key.clone(), next_value.clone());
+                    }
                 }
                 _ => {}
             }
 
-            Ok(())
-        });
-        isolate
-            .global()
-            .set("patchProp", rust_patch_prop)
-            .expect("Failed to set patchProp");
+            // Check for events (onClick, onHover)
+            let mut events_borrow = elements_events_clone.borrow_mut();
+            // create the events object if it doesn't exist
+            let events = events_borrow.entry(element).or_insert_with(|| Events {
+                click: None,
+                mouse_enter: None,
+                mouse_leave: None,
+                input: None,
+                update_open: None,
+                draw: None,
+                drop: None,
+                select: None,
+                trigger: None,
+                camera: None,
+                change: None,
+                commit: None,
+                key_down: None,
+                key_up: None,
+                focus: None,
+                blur: None,
+                shown: None,
+                hidden: None,
+                scroll: None,
+                submit: None,
+                wheel: None,
+                drag_start: None,
+                drag: None,
+                drag_end: None,
+                paste: None,
+                composition_start: None,
+                composition_update: None,
+                composition_end: None,
+                update_model_value: None,
+            });
+            // now add or remove the event
+            match key.as_str() {
+                "onClick" => {
+                    if next_value.is_function() {
+                        events.click = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.click = None;
+                    }
+                }
+                // onHover is the old name of onMouseEnter
+                "onMouseEnter" | "onHover" => {
+                    if next_value.is_function() {
+                        events.mouse_enter = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.mouse_enter = None;
+                    }
+                }
+                "onMouseLeave" => {
+                    if next_value.is_function() {
+                        events.mouse_leave = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.mouse_leave = None;
+                    }
+                }
+                "onInput" => {
+                    if next_value.is_function() {
+                        events.input = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.input = None;
+                    }
+                }
+                "onUpdate:open" => {
+                    if next_value.is_function() {
+                        events.update_open = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.update_open = None;
+                    }
+                }
+                "onDraw" => {
+                    if next_value.is_function() {
+                        events.draw = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.draw = None;
+                    }
+                }
+                "onDrop" => {
+                    if next_value.is_function() {
+                        events.drop = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.drop = None;
+                    }
+                }
+                "onSelect" => {
+                    if next_value.is_function() {
+                        events.select = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.select = None;
+                    }
+                }
+                "onTrigger" => {
+                    if next_value.is_function() {
+                        events.trigger = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.trigger = None;
+                    }
+                }
+                "onCamera" => {
+                    if next_value.is_function() {
+                        events.camera = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.camera = None;
+                    }
+                }
+                "onChange" => {
+                    if next_value.is_function() {
+                        events.change = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.change = None;
+                    }
+                }
+                "onCommit" => {
+                    if next_value.is_function() {
+                        events.commit = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.commit = None;
+                    }
+                }
+                "onKeyDown" => {
+                    if next_value.is_function() {
+                        events.key_down = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.key_down = None;
+                    }
+                }
+                "onKeyUp" => {
+                    if next_value.is_function() {
+                        events.key_up = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.key_up = None;
+                    }
+                }
+                "onFocus" => {
+                    if next_value.is_function() {
+                        events.focus = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.focus = None;
+                    }
+                }
+                "onBlur" => {
+                    if next_value.is_function() {
+                        events.blur = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.blur = None;
+                    }
+                }
+                "onShown" => {
+                    if next_value.is_function() {
+                        events.shown = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.shown = None;
+                    }
+                }
+                "onHidden" => {
+                    if next_value.is_function() {
+                        events.hidden = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.hidden = None;
+                    }
+                }
+                "onScroll" => {
+                    if next_value.is_function() {
+                        events.scroll = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.scroll = None;
+                    }
+                }
+                "onSubmit" => {
+                    if next_value.is_function() {
+                        events.submit = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.submit = None;
+                    }
+                }
+                "onWheel" => {
+                    if next_value.is_function() {
+                        events.wheel = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.wheel = None;
+                    }
+                }
+                "onDragStart" => {
+                    if next_value.is_function() {
+                        events.drag_start = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.drag_start = None;
+                    }
+                }
+                "onDrag" => {
+                    if next_value.is_function() {
+                        events.drag = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.drag = None;
+                    }
+                }
+                "onDragEnd" => {
+                    if next_value.is_function() {
+                        events.drag_end = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.drag_end = None;
+                    }
+                }
+                "onPaste" => {
+                    if next_value.is_function() {
+                        events.paste = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.paste = None;
+                    }
+                }
+                "onCompositionStart" => {
+                    if next_value.is_function() {
+                        events.composition_start = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.composition_start = None;
+                    }
+                }
+                "onCompositionUpdate" => {
+                    if next_value.is_function() {
+                        events.composition_update = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.composition_update = None;
+                    }
+                }
+                "onCompositionEnd" => {
+                    if next_value.is_function() {
+                        events.composition_end = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.composition_end = None;
+                    }
+                }
+                "onUpdate:modelValue" => {
+                    if next_value.is_function() {
+                        events.update_model_value = Some(next_value.as_function().unwrap().clone());
+                    } else {
+                        events.update_model_value = None;
+                    }
+                }
+                _ => {}
+            }
+
+            Ok(())
+        });
+        isolate
+            .global()
+            .set("patchProp", rust_patch_prop)
+            .expect("Failed to set patchProp");
+
+        // Set up the JS virtual machine
+        let vue_code = bundle.source()?;
+        isolate
+            .eval::<_, Value>(vue_code)
+            .expect("Failed to eval vue code");
+        let pinia_code = pinia_source()?;
+        let has_pinia = pinia_code.is_some();
+        if let Some(pinia_code) = pinia_code {
+            isolate
+                .eval::<_, Value>(pinia_code)
+                .map_err(|e| eyre::eyre!(format!("MiniV8 error: {:#?}", e)))?;
+        }
+
+        // Try to initialize the Vue app
+        let vue_init_code = r#"
+try {
+    const { createRenderer, defineComponent, h, reactive } = Vue;
+
+    console.log('Vue version:', Vue.version);
+    console.log('Testing console.log');
+    console.log('Small array:', [1, 2, 3]);
+    console.log('Small object:', { a: 1 });
+
+    const elementToId = new Map();
+    const idToElement = new Map();
+    let nextId = 1;
+    function getId(element) {
+        if (!elementToId.has(element)) {
+            elementToId.set(element, nextId++);
+        }
+        return elementToId.get(element);
+    }
+    function getElementById(id) {
+        return idToElement.get(id);
+    }
+
+    // v-show toggles `el.style.display`, which is forwarded as the `visible` prop
+    function createStyle(id) {
+        let display = '';
+        return {
+            get display() {
+                return display;
+            },
+            set display(value) {
+                display = value;
+                patchProp(id, 'visible', undefined, value !== 'none');
+            },
+        };
+    }
+
+    // The script side of an element created in Rust, text and comment nodes included, so
+    // parentNode and nextSibling can hand every node back
+    function wrapElement(id) {
+        const element = {
+            id,
+            style: createStyle(id),
+            focus() {
+                focusElement(id);
+            },
+            getAttribute(key) {
+                return getAttribute(id, key);
+            },
+            // goes through patchProp, so known props update the element as well
+            setAttribute(key, value) {
+                patchProp(id, key, getAttribute(id, key), value);
+            },
+            cloneNode(deep) {
+                return nodeOps.cloneNode(element, deep);
+            },
+        };
+        elementToId.set(element, id);
+        idToElement.set(id, element);
+        return element;
+    }
+
+    // Creates a parsed static node the way the render function would have, text-only children
+    // become the element's text
+    function mountStaticNode(node, parent, anchor) {
+        let el;
+        if (node.type === 'text') {
+            el = nodeOps.createText(node.text);
+        } else if (node.type === 'comment') {
+            el = nodeOps.createComment(node.text);
+        } else {
+            el = nodeOps.createElement(node.tag);
+            for (const [key, value] of node.attributes) {
+                nodeOps.patchProp(el, key, null, value);
+            }
+            if (node.children.every(child => child.type === 'text')) {
+                if (node.children.length > 0) {
+                    nodeOps.setElementText(el, node.children.map(child => child.text).join(''));
+                }
+            } else {
+                for (const child of node.children) {
+                    mountStaticNode(child, el, null);
+                }
+            }
+        }
+        nodeOps.insert(el, parent, anchor);
+        return el;
+    }
+
+    const nodeOps = {
+        // Create a node in the non-DOM environment
+        createElement(tag) {
+            const id = nextId++;
+            return wrapElement(createElement(id, tag));
+        },
+        // Insert child into parent, possibly using some custom API
+        insert(child, parent, anchor) {
+            insertElement(child.id, parent.id, anchor?.id);
+        },
+        // Remove an element, adapting to your backend's capabilities
+        remove(child) {
+            removeElement(child.id);
+        },
+        createText(text) {
+            const id = nextId++;
+            const element = wrapElement(createElement(id, 'hidden'));
+            setElementText(element.id, text);
+            return element;
+        },
+        createComment(text) {
+            const id = nextId++;
+            const element = wrapElement(createElement(id, 'comment'));
+            setElementText(element.id, text);
+            return element;
+        },
+        // The copy isn't attached anywhere, `deep` copies the children as well
+        cloneNode(el, deep = false) {
+            const ids = cloneElement(el.id, nextId, !!deep);
+            nextId += ids.length;
+            ids.forEach(wrapElement);
+            return idToElement.get(ids[0]);
+        },
+        setText(node, text) {
+            console.log('Setting text for node:', node);
+            setElementText(node.id, text);
+        },
+        setElementText(el, text) {
+            setElementText(el.id, text);
+        },
+        patchProp(el, key, prevValue, nextValue) {
+            patchProp(el.id, key, prevValue, nextValue);
+        },
+        parentNode(node) {
+            return getElementById(parentNode(node.id));
+        },
+        nextSibling(node) {
+            return getElementById(nextSibling(node.id));
+        },
+        querySelector(selector) {
+            return getElementById(querySelector(selector)) ?? null;
+        },
+        // `id` is a component's `__scopeId`, as set by SFC compilers for `<style scoped>`
+        setScopeId(el, id) {
+            setScopeId(el.id, id);
+        },
+        // Static chunks of a template come as markup, a remount clones the previous mount's
+        // nodes instead of parsing again
+        insertStaticContent(content, parent, anchor, namespace, start, end) {
+            const inserted = [];
+            if (start && (start === end || nodeOps.nextSibling(start))) {
+                while (true) {
+                    const clone = nodeOps.cloneNode(start, true);
+                    nodeOps.insert(clone, parent, anchor);
+                    inserted.push(clone);
+                    if (start === end || !(start = nodeOps.nextSibling(start))) {
+                        break;
+                    }
+                }
+            } else {
+                for (const node of parseStaticContent(content)) {
+                    inserted.push(mountStaticNode(node, parent, anchor));
+                }
+            }
+            return [inserted[0], inserted[inserted.length - 1]];
+        },
+    };
+
+    // v-model on any value widget, the bound value is patched into the element as its
+    // `modelValue` prop and the element emits `update:modelValue` itself, so none of the DOM
+    // listeners of Vue's own directives are needed
+    const vModel = {
+        mounted(el, { value }) {
+            patchProp(el.id, 'modelValue', undefined, value);
+        },
+        beforeUpdate(el, { value, oldValue }) {
+            if (value !== oldValue) {
+                patchProp(el.id, 'modelValue', oldValue, value);
+            }
+        },
+    };
+    // the compiler picks one of these by the element's `type`, they all work the same here
+    Vue.vModelText = vModel;
+    Vue.vModelCheckbox = vModel;
+    Vue.vModelRadio = vModel;
+    Vue.vModelSelect = vModel;
+    Vue.vModelDynamic = vModel;
+
+    // <Transition> is built on BaseTransition, the renderer does the animating: `name` picks a
+    // preset and `duration` is in milliseconds, either a number or `{ enter, leave }`
+    const transitionOffsets = {
+        fade: [0, 0],
+        'slide-up': [0, 16],
+        'slide-down': [0, -16],
+        'slide-left': [16, 0],
+        'slide-right': [-16, 0],
+    };
+    Vue.Transition = (props, { slots }) => {
+        const { name = 'fade', duration = 200, onEnter, onLeave, ...hooks } = props;
+        const [offsetX, offsetY] = transitionOffsets[name] ?? transitionOffsets.fade;
+        const enterDuration = Number(duration?.enter ?? duration);
+        const leaveDuration = Number(duration?.leave ?? duration);
+        return h(Vue.BaseTransition, {
+            ...hooks,
+            onEnter(el, done) {
+                onEnter?.(el);
+                animateElement(el.id, 'enter', enterDuration, offsetX, offsetY, done);
+            },
+            onLeave(el, done) {
+                onLeave?.(el);
+                animateElement(el.id, 'leave', leaveDuration, offsetX, offsetY, done);
+            },
+        }, slots);
+    };
+
+    // A router in the shape of vue-router's API with memory history, as there's no URL bar:
+    // routes map paths with `:param` segments to components, <router-view> shows the current
+    // route's component and <router-link> navigates
+    const routerKey = Symbol('router');
+    const routeKey = Symbol('route');
+    function parseQuery(search) {
+        const query = {};
+        for (const pair of search.split('&').filter(Boolean)) {
+            const [key, value = ''] = pair.split('=').map(decodeURIComponent);
+            query[key] = value;
+        }
+        return query;
+    }
+    function createMemoryHistory(base = '') {
+        return { base };
+    }
+    function createRouter({ routes, history = createMemoryHistory() }) {
+        const records = routes.map(route => {
+            const names = [];
+            const source = route.path
+                .replace(/\/+$/, '')
+                .replace(/:(\w+)/g, (_, name) => {
+                    names.push(name);
+                    return '([^/]+)';
+                });
+            return { route, names, pattern: new RegExp(`^${source}/?$`) };
+        });
+
+        function resolve(to) {
+            const location = typeof to === 'string' ? { path: to } : to;
+            let path = location.path;
+            if (path === undefined && location.name !== undefined) {
+                const record = records.find(record => record.route.name === location.name);
+                if (!record) {
+                    throw new Error(`No route named ${location.name}`);
+                }
+                path = record.route.path.replace(/:(\w+)/g, (_, name) =>
+                    encodeURIComponent(location.params?.[name] ?? ''));
+            }
+            const [pathname, search = ''] = (path ?? '/').split('?');
+            const query = { ...parseQuery(search), ...location.query };
+            for (const { route, names, pattern } of records) {
+                const match = pathname.match(pattern);
+                if (match) {
+                    const params = Object.fromEntries(
+                        names.map((name, index) => [name, decodeURIComponent(match[index + 1])]));
+                    if (route.redirect !== undefined) {
+                        return resolve(typeof route.redirect === 'function'
+                            ? route.redirect({ path: pathname, params, query })
+                            : route.redirect);
+                    }
+                    return { path: pathname, fullPath: path, name: route.name, params, query, meta: route.meta ?? {}, matched: [route] };
+                }
+            }
+            return { path: pathname, fullPath: path, name: undefined, params: {}, query, meta: {}, matched: [] };
+        }
+
+        const entries = [resolve(history.base || '/')];
+        let index = 0;
+        const currentRoute = Vue.shallowRef(entries[0]);
+        // what useRoute() hands out, the same object for the router's whole lifetime
+        const reactiveRoute = Vue.shallowReactive({ ...entries[0] });
+        const guards = [];
+        const afterHooks = [];
+
+        // Guards may cancel with `false` or redirect by returning another location
+        function navigate(to, commit) {
+            let route = resolve(to);
+            const from = currentRoute.value;
+            for (const guard of guards) {
+                const result = guard(route, from);
+                if (result === false) {
+                    return Promise.resolve(false);
+                }
+                if (result !== undefined && result !== true) {
+                    route = resolve(result);
+                }
+            }
+            commit(route);
+            currentRoute.value = route;
+            Object.assign(reactiveRoute, route);
+            afterHooks.forEach(hook => hook(route, from));
+            return Promise.resolve();
+        }
+        function go(delta) {
+            const target = index + delta;
+            if (target < 0 || target >= entries.length) {
+                return Promise.resolve(false);
+            }
+            return navigate(entries[target].fullPath, () => {
+                index = target;
+            });
+        }
+
+        const router = {
+            currentRoute,
+            resolve,
+            push(to) {
+                return navigate(to, route => {
+                    entries.splice(index + 1, Infinity, route);
+                    index++;
+                });
+            },
+            replace(to) {
+                return navigate(to, route => {
+                    entries[index] = route;
+                });
+            },
+            go,
+            back: () => go(-1),
+            forward: () => go(1),
+            beforeEach(guard) {
+                guards.push(guard);
+                return () => guards.splice(guards.indexOf(guard), 1);
+            },
+            afterEach(hook) {
+                afterHooks.push(hook);
+                return () => afterHooks.splice(afterHooks.indexOf(hook), 1);
+            },
+            install(app) {
+                app.provide(routerKey, router);
+                app.provide(routeKey, reactiveRoute);
+                app.config.globalProperties.$router = router;
+                Object.defineProperty(app.config.globalProperties, '$route', {
+                    get: () => currentRoute.value,
+                });
+                app.component('RouterView', RouterView);
+                app.component('RouterLink', RouterLink);
+            },
+        };
+        return router;
+    }
+    function useRouter() {
+        return Vue.inject(routerKey);
+    }
+    function useRoute() {
+        return Vue.inject(routeKey);
+    }
+    const RouterView = defineComponent({
+        name: 'RouterView',
+        setup(_, { attrs }) {
+            const router = useRouter();
+            return () => {
+                const route = router.currentRoute.value;
+                const record = route.matched[0];
+                if (!record?.component) {
+                    return null;
+                }
+                const props = record.props === true ? route.params : {};
+                return h(record.component, { ...attrs, ...props });
+            };
+        },
+    });
+    // A link whose text is the slot's text, clicking it navigates instead of opening `href`
+    const RouterLink = defineComponent({
+        name: 'RouterLink',
+        props: { to: { type: [String, Object], required: true }, replace: Boolean },
+        setup(props, { slots }) {
+            const router = useRouter();
+            return () => {
+                const target = router.resolve(props.to);
+                const text = (slots.default?.() ?? [])
+                    .map(vnode => typeof vnode.children === 'string' ? vnode.children : '')
+                    .join('');
+                return h('link', {
+                    href: target.fullPath,
+                    onClick: () => (props.replace ? router.replace : router.push)(props.to),
+                }, text);
+            };
+        },
+    });
+    globalThis.VueRouter = {
+        createRouter,
+        createMemoryHistory,
+        useRouter,
+        useRoute,
+        RouterView,
+        RouterLink,
+    };
+
+    // The host's view of the stores of the active Pinia, by id, a store's state is there once it
+    // was first used. Without Pinia there's no `vgui.stores`
+    if (globalThis.Pinia) {
+        vgui.stores = {
+            state(id) {
+                return Pinia.getActivePinia()?.state.value[id];
+            },
+            patch(id, partial) {
+                const store = Pinia.getActivePinia()?._s.get(id);
+                if (!store) {
+                    throw new Error(`Store ${id} isn't in use`);
+                }
+                store.$patch(partial);
+            },
+            watch(id, callback) {
+                return Vue.watch(() => Pinia.getActivePinia()?.state.value[id], state => {
+                    if (state !== undefined) {
+                        callback(state);
+                    }
+                }, { deep: true });
+            },
+        };
+    }
+
+    const { render, createApp: createRendererApp } = createRenderer(nodeOps);
+    // vgui's tags and the registered elements are host elements, not components
+    const isCustomElement = tag => {
+        console.log(`Checking if ${tag} is a custom element`);
+        return [
+            'label',
+            'vertical',
+            'horizontal',
+            'button',
+            'hidden',
+            'comment',
+            'separator',
+            'text-edit',
+            'checkbox',
+            'slider',
+            'radio-group',
+            'radio',
+            'progress',
+            'image',
+            'link',
+            'collapsing',
+            'window',
+            'grid',
+            'row',
+            'tabs',
+            'tab',
+            'table',
+            'text-area',
+            'number',
+            'spinner',
+            'menu-bar',
+            'menu',
+            'menu-item',
+            'context-menu',
+            'plot',
+            'canvas',
+            'frame',
+            'side-panel',
+            'top-panel',
+            'bottom-panel',
+            'markdown',
+            'spacer',
+            'split',
+            'svg',
+            'gauge',
+            'segmented',
+            'drop-zone',
+            'wrap',
+            'scroll',
+            'columns',
+            'area',
+            'selectable-label',
+            'b',
+            'i',
+            'color',
+            'image-button',
+            'shortcut',
+            'sparkline',
+            'viewport3d',
+        ].includes(tag) || isRegisteredElement(tag);
+    };
+
+    // Like runtime-dom's, `app.mount('#app')` takes a selector for the container as well
+    const createApp = (...args) => {
+        const app = createRendererApp(...args);
+        app.config.isCustomElement = isCustomElement;
+        const { mount } = app;
+        app.mount = (container, ...rest) => {
+            if (typeof container === 'string') {
+                const selector = container;
+                container = nodeOps.querySelector(selector);
+                if (!container) {
+                    throw new Error(`No element matches ${selector}`);
+                }
+            }
+            return mount(container, ...rest);
+        };
+        return app;
+    };
+    // `Vue.createApp` and the `vue` module's `createApp` create apps rendered by vgui
+    Vue.createApp = createApp;
+    const { watch, ref } = Vue;
+
+    // A widget defined in script, `ui` lays out labels, frames and the element's own children
+    vgui.registerElement('badge', {
+        render(props, children, ui) {
+            ui.frame({ fill: props.color ?? '#3b6fd4', rounding: 8, padding: 4 }, () => {
+                ui.horizontal(() => {
+                    ui.label(String(props.text ?? ''), { color: 'white', strong: true });
+                    ui.children();
+                });
+            });
+        },
+    });
+
+    // The zoom buttons need Pinia, they're left out without it
+    const useSettings = globalThis.Pinia && Pinia.defineStore('settings', {
+        state: () => ({ zoom: 1 }),
+        actions: {
+            zoomIn() {
+                this.zoom = Math.min(this.zoom + 0.1, 3);
+            },
+            zoomOut() {
+                this.zoom = Math.max(this.zoom - 0.1, 0.5);
+            },
+        },
+    });
+
+    const App = {
+        setup() {
+            console.log('App setup:');
+            const settings = useSettings ? useSettings() : null;
+
+            const value = ref(0);
+            const stringValue = ref('Hello, Vue!');
+            const additionalControls = ref(false);
+
+            const label = ref(null);
+            watch(label, (value) => {
+                console.log('Label changed:', value);
+            });
+
+            return { value, additionalControls, label, stringValue, settings };
+        },
+        template: `
+            <vertical>
+                <label ref="label">Value: {{ value }}</label>
+                <badge :text="value >= 0 ? 'positive' : 'negative'"></badge>
+                <button @click="value++">Increment</button>
+                <button @click="value = 0" tooltip="Sets the value back to zero">Reset</button>
+                <label>String Value: {{ stringValue }}</label>
+                <horizontal v-if="settings">
+                    <button @click="settings.zoomOut()">-</button>
+                    <label>Zoom: {{ Math.round(settings.zoom * 100) }}%</label>
+                    <button @click="settings.zoomIn()">+</button>
+                </horizontal>
+
+                // Additional controls
+                <button @click="additionalControls = !additionalControls">Toggle Controls</button>
+                <checkbox v-model="additionalControls">Show additional controls</checkbox>
+                <vertical v-if="additionalControls">
+                    <label>Additional Controls</label>
+                    <button @click="value--">Decrement</button>
+                    <slider v-model="value" :min="-10" :max="10" :step="1">Value</slider>
+                    <progress :value="(value + 10) / 20" :text="`${value}`"></progress>
+                    <text-edit v-model="stringValue"></text-edit>
+                </vertical>
+                <separator></separator>
+            </vertical>
+        `,
+    };
+
+    // The 'root' object would represent the top level of your app
+    const root = { id: 0 };
+    elementToId.set(root, 0);
+    idToElement.set(0, root);
+    console.log(`Root object created:`, root);
+    const unmountedApp = createApp(App);
+    if (globalThis.Pinia) {
+        unmountedApp.use(Pinia.createPinia());
+    }
+    // An app module (`--app`) creates and mounts its own app instead
+    if (!vgui.entry) {
+        unmountedApp.mount(root);
+    }
+} catch (e) {
+    const errorMessage = `Error Message: ${e.message}`;
+    const stackTrace = `Stack Trace:\n${e.stack}`;
+
+    console.error(`${errorMessage}\n${stackTrace}`);
+}
+"#;
+
+        isolate
+            .eval::<_, Value>(vue_init_code)
+            .map_err(|e| eyre::eyre!(format!("MiniV8 error: {:#?}", e)))?;
+
+        // The app module and what it imports, `vue`, `vue-router` and `pinia` (when it's shipped)
+        // are the globals
+        if let Some(entry) = entry {
+            let entry = std::env::current_dir()
+                .wrap_err("Failed to get the current directory")?
+                .join(entry);
+            let mut builtins = HashMap::new();
+            for (specifier, global) in [
+                ("vue", "Vue"),
+                ("vue-router", "VueRouter"),
+                ("pinia", "Pinia"),
+            ] {
+                if global == "Pinia" && !has_pinia {
+                    continue;
+                }
+                let names: Vec<String> = isolate
+                    .eval(format!("Object.keys({})", global))
+                    .map_err(|e| eyre::eyre!(format!("MiniV8 error: {:#?}", e)))?;
+                builtins.insert(
+                    specifier.to_string(),
+                    modules::global_module(global, &names),
+                );
+            }
+            let root = entry.parent().map(Path::to_path_buf).unwrap_or_default();
+            isolate.set_module_loader(FileLoader::new(&root, builtins));
+            isolate
+                .import_module(&entry.to_string_lossy())
+                .map_err(|e| {
+                    eyre::eyre!(format!("Failed to import {}: {:#?}", entry.display(), e))
+                })?;
+        }
+
+        let canvas = CanvasContext::new(&isolate);
+        icons::register_font(&cc.egui_ctx);
+        let mut viewports = ViewportRegistry::default();
+        match cc.wgpu_render_state.as_ref() {
+            Some(render_state) => viewports.register("triangle", TrianglePlugin::new(render_state)),
+            None => println!("Error: wgpu isn't available, viewport3d renderers are disabled"),
+        }
+        let this = Self {
+            isolate,
+            elements,
+            elements_children,
+            element_events,
+            element_props,
+            textures: TextureRegistry::default(),
+            canvas,
+            markdown_cache: RefCell::new(egui_commonmark::CommonMarkCache::default()),
+            svg_cache: RefCell::new(SvgCache::default()),
+            frame_input: RefCell::new(FrameInput::default()),
+            rendered_elements: RefCell::new(RenderedElements::default()),
+            hovered_elements: RefCell::new(HashSet::new()),
+            drag_totals: RefCell::new(HashMap::new()),
+            viewports,
+            theme,
+            styles,
+            window,
+            shortcuts,
+            global_hotkeys,
+            focus_requests,
+            animations,
+            tooltip_delay,
+            tree_changed,
+            custom_elements,
+            zoom_binding: RefCell::new(None),
+            requested_zoom: Rc::new(Cell::new(None)),
+            zoom: Cell::new(1.0),
+            timers,
+            animation_frames,
+            fetch,
+        };
+        // The demo's zoom buttons go through its `settings` store, app modules bring their own
+        if entry.is_none() && has_pinia {
+            this.bind_zoom("settings", "zoom")?;
+        }
+        this.print_tree(0, 0);
+        Ok(this)
+    }
+
+    // Element related functions
+    fn print_tree(&self, element_id: ElementId, depth: usize) {
+        let indent = "  ".repeat(depth);
+        let elements_borrow = self.elements.borrow();
+        let element_ref = elements_borrow
+            .get(&element_id)
+            .expect("Failed to get element");
+        let element = element_ref.borrow();
+        match &*element {
+            Element::Root => {
+                println!("{}Root({})", indent, element_id);
+            }
+            Element::Label(label) => {
+                println!("{}Label({}): {}", indent, element_id, label);
+            }
+            Element::Button(label) => {
+                println!("{}Button({}): {}", indent, element_id, label);
+            }
+            Element::Vertical(layout_options) => {
+                println!("{}Vertical({}): {:?}", indent, element_id, layout_options);
+            }
+            Element::Horizontal(layout_options) => {
+                println!("{}Horizontal({}): {:?}", indent, element_id, layout_options);
+            }
+            Element::Hidden(label) => {
+                println!("{}Hidden({}): {}", indent, element_id, label);
+            }
+            Element::Comment(comment) => {
+                println!("{}Comment({}): {}", indent, element_id, comment);
+            }
+            Element::Separator => {
+                println!("{}Separator({})", indent, element_id);
+            }
+            Element::TextEdit { text, password, .. } => {
+                let text = if *password {
+                    "*".repeat(text.len())
+                } else {
+                    text.clone()
+                };
+                println!("{}TextEdit({}): {}", indent, element_id, text);
+            }
+            Element::Slider {
+                text,
+                value,
+                min,
+                max,
+                ..
+            } => {
+                println!(
+                    "{}Slider({}): {} [{} in {}..={}]",
+                    indent, element_id, text, value, min, max
+                );
+            }
+            Element::RadioGroup(selected) => {
+                println!("{}RadioGroup({}): {}", indent, element_id, selected);
+            }
+            Element::Radio { text, value } => {
+                println!("{}Radio({}): {} [{}]", indent, element_id, text, value);
+            }
+            Element::Progress { value, text } => {
+                println!("{}Progress({}): {} {:?}", indent, element_id, value, text);
+            }
+            Element::Image(src) => {
+                println!("{}Image({}): {}", indent, element_id, src);
+            }
+            Element::ImageButton { src, selected, .. } => {
+                println!(
+                    "{}ImageButton({}): {} [selected: {}]",
+                    indent, element_id, src, selected
+                );
+            }
+            Element::Link { text, href } => {
+                println!("{}Link({}): {} -> {}", indent, element_id, text, href);
+            }
+            Element::Collapsing {
+                title,
+                default_open,
+            } => {
+                println!(
+                    "{}Collapsing({}): {} [open: {}]",
+                    indent, element_id, title, default_open
+                );
+            }
+            Element::Window { title, open, .. } => {
+                println!(
+                    "{}Window({}): {} [open: {}]",
+                    indent, element_id, title, open
+                );
+            }
+            Element::Grid {
+                columns, striped, ..
+            } => {
+                println!(
+                    "{}Grid({}): {:?} columns [striped: {}]",
+                    indent, element_id, columns, striped
+                );
+            }
+            Element::Row => {
+                println!("{}Row({})", indent, element_id);
+            }
+            Element::Tabs(active) => {
+                println!("{}Tabs({}): active {}", indent, element_id, active);
+            }
+            Element::Tab(title) => {
+                println!("{}Tab({}): {}", indent, element_id, title);
+            }
+            Element::Table { columns, .. } => {
+                let titles: Vec<&str> = columns.iter().map(|c| c.title.as_str()).collect();
+                println!("{}Table({}): {:?}", indent, element_id, titles);
+            }
+            Element::TextArea { text, rows, .. } => {
+                println!(
+                    "{}TextArea({}): {} [rows: {}]",
+                    indent, element_id, text, rows
+                );
+            }
+            Element::Number { value, format, .. } => {
+                println!(
+                    "{}Number({}): {}{}{}",
+                    indent, element_id, format.prefix, value, format.suffix
+                );
+            }
+            Element::Spinner(size) => {
+                println!("{}Spinner({}): {:?}", indent, element_id, size);
+            }
+            Element::MenuBar => {
+                println!("{}MenuBar({})", indent, element_id);
+            }
+            Element::Menu(title) => {
+                println!("{}Menu({}): {}", indent, element_id, title);
+            }
+            Element::MenuItem(label) => {
+                println!("{}MenuItem({}): {}", indent, element_id, label);
+            }
+            Element::ContextMenu => {
+                println!("{}ContextMenu({})", indent, element_id);
+            }
+            Element::Plot { series, .. } => {
+                println!("{}Plot({}): {} series", indent, element_id, series.len());
+            }
+            Element::Canvas(size) => {
+                println!("{}Canvas({}): {:?}", indent, element_id, size);
+            }
+            Element::Frame { .. } => {
+                println!("{}Frame({})", indent, element_id);
+            }
+            Element::Panel { side, .. } => {
+                println!("{}Panel({}): {:?}", indent, element_id, side);
+            }
+            Element::Markdown(source) => {
+                println!("{}Markdown({}): {} bytes", indent, element_id, source.len());
+            }
+            Element::Spacer {
+                width,
+                height,
+                grow,
+            } => {
+                println!(
+                    "{}Spacer({}): {:?}x{:?} [grow: {}]",
+                    indent, element_id, width, height, grow
+                );
+            }
+            Element::Split { ratio, vertical } => {
+                println!(
+                    "{}Split({}): {} [vertical: {}]",
+                    indent, element_id, ratio, vertical
+                );
+            }
+            Element::Svg { src, .. } => {
+                let src = if src.trim_start().starts_with('<') {
+                    "<inline>"
+                } else {
+                    src.as_str()
+                };
+                println!("{}Svg({}): {}", indent, element_id, src);
+            }
+            Element::Gauge {
+                value, min, max, ..
+            } => {
+                println!(
+                    "{}Gauge({}): {} in {}..={}",
+                    indent, element_id, value, min, max
+                );
+            }
+            Element::Segmented { selected, options } => {
+                let values: Vec<&str> = options.iter().map(|o| o.value.as_str()).collect();
+                println!(
+                    "{}Segmented({}): {} in {:?}",
+                    indent, element_id, selected, values
+                );
+            }
+            Element::DropZone => {
+                println!("{}DropZone({})", indent, element_id);
+            }
+            Element::Wrap => {
+                println!("{}Wrap({})", indent, element_id);
+            }
+            Element::Scroll {
+                horizontal,
+                vertical,
+                offset,
+                ..
+            } => {
+                println!(
+                    "{}Scroll({}): {:?} [horizontal: {}, vertical: {}]",
+                    indent, element_id, offset, horizontal, vertical
+                );
+            }
+            Element::Columns(count) => {
+                println!("{}Columns({}): {:?}", indent, element_id, count);
+            }
+            Element::Area {
+                position,
+                draggable,
+            } => {
+                println!(
+                    "{}Area({}): {:?} [draggable: {}]",
+                    indent, element_id, position, draggable
+                );
+            }
+            Element::SelectableLabel { text, .. } => {
+                println!("{}SelectableLabel({}): {}", indent, element_id, text);
+            }
+            Element::Shortcut(shortcut) => {
+                let keys =
+                    shortcut.map(|shortcut| shortcut.format(&egui::ModifierNames::NAMES, false));
+                println!("{}Shortcut({}): {:?}", indent, element_id, keys);
+            }
+            Element::Sparkline { points, .. } => {
+                println!(
+                    "{}Sparkline({}): {} points",
+                    indent,
+                    element_id,
+                    points.len()
+                );
+            }
+            Element::Viewport3d {
+                renderer, camera, ..
+            } => {
+                println!(
+                    "{}Viewport3d({}): {} {:?}",
+                    indent, element_id, renderer, camera
+                );
+            }
+            Element::Span(span, text) => {
+                println!("{}Span({}): {} {:?}", indent, element_id, text, span);
+            }
+            Element::Checkbox(label, checked) => {
+                println!(
+                    "{}Checkbox({}): {} [{}]",
+                    indent, element_id, label, checked
+                );
+            }
+            Element::Custom(tag) => {
+                println!("{}Custom({}): {}", indent, element_id, tag);
+            }
+        }
+
+        let elements_children_borrow = self.elements_children.borrow();
+        let children = elements_children_borrow.get(&element_id);
+        if let Some(children) = children {
+            for child_id in children {
+                self.print_tree(*child_id, depth + 1);
+            }
+        }
+
+        println!("{}End", indent);
+    }
+
+    // Collects all elements matching the predicate, in tree order
+    fn find_elements(&self, predicate: impl Fn(&Element) -> bool) -> Vec<ElementId> {
+        let elements_borrow = self.elements.borrow();
+        let elements_children_borrow = self.elements_children.borrow();
+        let mut found = Vec::new();
+        let mut stack = vec![0];
+        while let Some(element_id) = stack.pop() {
+            let element_ref = elements_borrow
+                .get(&element_id)
+                .expect("Failed to get element");
+            if predicate(&element_ref.borrow()) {
+                found.push(element_id);
+            }
+            if let Some(children) = elements_children_borrow.get(&element_id) {
+                stack.extend(children.iter().rev());
+            }
+        }
+        found
+    }
+
+    // Shortcuts render nothing, they are checked against the input once per frame
+    fn run_shortcuts(&self, ctx: &egui::Context) {
+        let shortcut_ids = self.find_elements(|element| matches!(element, Element::Shortcut(_)));
+        for shortcut_id in shortcut_ids {
+            let shortcut = {
+                let elements_borrow = self.elements.borrow();
+                let element_ref = elements_borrow
+                    .get(&shortcut_id)
+                    .expect("Failed to get shortcut");
+                let shortcut = match &*element_ref.borrow() {
+                    Element::Shortcut(shortcut) => *shortcut,
+                    _ => unreachable!("Expected a shortcut element"),
+                };
+                shortcut
+            };
+            let Some(shortcut) = shortcut else {
+                continue;
+            };
+            if !ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+                continue;
+            }
+
+            let element_events_borrow = self.element_events.borrow();
+            let trigger = element_events_borrow
+                .get(&shortcut_id)
+                .and_then(|events| events.trigger.as_ref());
+            if let Some(trigger) = trigger {
+                call_handler(&self.isolate, trigger, "trigger", Vec::new());
+            }
+        }
+    }
+
+    // Menu bars are not part of the central layout, they get their own top panel
+    fn render_menu_bars(&self, ctx: &egui::Context) {
+        let menu_bars = self.find_elements(|element| matches!(element, Element::MenuBar));
+        if menu_bars.is_empty() {
+            return;
+        }
+
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                for menu_bar_id in menu_bars {
+                    self.render_children(ui, menu_bar_id);
+                }
+            });
+        });
+    }
+
+    // Panels are not part of the central layout, they have to be shown before the CentralPanel
+    fn render_panels(&self, ctx: &egui::Context) {
+        let mut panels: Vec<(ElementId, PanelSide, bool)> = self
+            .find_elements(|element| matches!(element, Element::Panel { .. }))
+            .into_iter()
+            .map(|panel_id| {
+                let elements_borrow = self.elements.borrow();
+                let element_ref = elements_borrow.get(&panel_id).expect("Failed to get panel");
+                let panel = match &*element_ref.borrow() {
+                    Element::Panel { side, resizable } => (panel_id, *side, *resizable),
+                    _ => unreachable!("Expected a panel element"),
+                };
+                panel
+            })
+            .collect();
+        // top and bottom panels go first, so they span the whole window width
+        panels.sort_by_key(|(_, side, _)| matches!(side, PanelSide::Left | PanelSide::Right));
+
+        for (panel_id, side, resizable) in panels {
+            let id = egui::Id::new(("panel", panel_id));
+            let add_contents = |ui: &mut egui::Ui| {
+                self.render_children(ui, panel_id);
+            };
+            match side {
+                PanelSide::Left => {
+                    egui::SidePanel::left(id)
+                        .resizable(resizable)
+                        .show(ctx, add_contents);
+                }
+                PanelSide::Right => {
+                    egui::SidePanel::right(id)
+                        .resizable(resizable)
+                        .show(ctx, add_contents);
+                }
+                PanelSide::Top => {
+                    egui::TopBottomPanel::top(id)
+                        .resizable(resizable)
+                        .show(ctx, add_contents);
+                }
+                PanelSide::Bottom => {
+                    egui::TopBottomPanel::bottom(id)
+                        .resizable(resizable)
+                        .show(ctx, add_contents);
+                }
+            }
+        }
+    }
+
+    // A store of the app's Pinia instance, by its id
+    fn store(&self, id: &str) -> Store {
+        Store::new(&self.isolate, id)
+    }
+
+    // Binds `field` of the store `id` to egui's zoom factor: the field drives the zoom, and
+    // zooming with the keyboard is written back to the store
+    fn bind_zoom(&self, id: &str, field: &str) -> Result<()> {
+        let store = self.store(id);
+        let field_name = field.to_string();
+        let zoom_of = move |state: &Object| {
+            state
+                .get::<_, Option<f32>>(field_name.clone())
+                .ok()
+                .flatten()
+        };
+        let state = store
+            .state()
+            .map_err(|e| eyre::eyre!(format!("MiniV8 error: {:#?}", e)))?;
+        self.requested_zoom.set(state.as_ref().and_then(&zoom_of));
+        let requested_zoom = self.requested_zoom.clone();
+        store
+            .watch(move |_, state| {
+                if let Some(zoom) = zoom_of(&state) {
+                    requested_zoom.set(Some(zoom));
+                }
+            })
+            .map_err(|e| eyre::eyre!(format!("MiniV8 error: {:#?}", e)))?;
+        *self.zoom_binding.borrow_mut() = Some((store, field.to_string()));
+        Ok(())
+    }
+
+    fn sync_zoom(&self, ctx: &egui::Context) {
+        let zoom_binding = self.zoom_binding.borrow();
+        let Some((store, field)) = zoom_binding.as_ref() else {
+            return;
+        };
+        if let Some(zoom) = self.requested_zoom.take() {
+            ctx.set_zoom_factor(zoom);
+            self.zoom.set(zoom);
+        }
+        let zoom = ctx.zoom_factor();
+        if zoom != self.zoom.get() {
+            self.zoom.set(zoom);
+            let partial = self.isolate.create_object();
+            partial
+                .set(field.as_str(), zoom)
+                .expect("Failed to set zoom");
+            if let Err(error) = store.patch(partial) {
+                report_error(&self.isolate, "zoom", error);
+            }
+        }
+    }
+
+    // Replays what the render function of a registered element recorded
+    fn render_ui_commands(
+        &self,
+        ui: &mut egui::Ui,
+        element_id: ElementId,
+        commands: &[UiCommand],
+    ) -> Vec<Response> {
+        let mut responses = Vec::new();
+        for command in commands {
+            match command {
+                UiCommand::Label {
+                    text,
+                    color,
+                    size,
+                    strong,
+                } => {
+                    let mut rich_text = egui::RichText::new(text);
+                    if let Some(color) = color {
+                        rich_text = rich_text.color(*color);
+                    }
+                    if let Some(size) = size {
+                        rich_text = rich_text.size(*size);
+                    }
+                    if *strong {
+                        rich_text = rich_text.strong();
+                    }
+                    responses.push(ui.label(rich_text));
+                }
+                UiCommand::Space(amount) => ui.add_space(*amount),
+                UiCommand::Separator => {
+                    ui.separator();
+                }
+                UiCommand::Children => {
+                    responses.extend(self.render_children(ui, element_id));
+                }
+                UiCommand::Child(child_id) => {
+                    let is_child = self
+                        .elements_children
+                        .borrow()
+                        .get(&element_id)
+                        .map_or(false, |children| children.contains(child_id));
+                    if is_child {
+                        responses.extend(self.render_element(ui, *child_id));
+                    }
+                }
+                UiCommand::Horizontal(commands) => {
+                    let inner =
+                        ui.horizontal(|ui| self.render_ui_commands(ui, element_id, commands));
+                    responses.extend(inner.inner);
+                }
+                UiCommand::Vertical(commands) => {
+                    let inner = ui.vertical(|ui| self.render_ui_commands(ui, element_id, commands));
+                    responses.extend(inner.inner);
+                }
+                UiCommand::Frame {
+                    fill,
+                    stroke,
+                    rounding,
+                    padding,
+                    commands,
+                } => {
+                    let mut frame = egui::Frame::none()
+                        .rounding(*rounding)
+                        .inner_margin(*padding);
+                    if let Some(fill) = fill {
+                        frame = frame.fill(*fill);
+                    }
+                    if let Some(stroke) = stroke {
+                        frame = frame.stroke(egui::Stroke::new(1.0, *stroke));
+                    }
+                    let inner =
+                        frame.show(ui, |ui| self.render_ui_commands(ui, element_id, commands));
+                    responses.extend(inner.inner);
+                }
+            }
+        }
+        responses
+    }
+
+    // Text nodes and comments Vue inserts as anchors, they never render anything
+    fn is_placeholder(&self, element_id: ElementId) -> bool {
+        self.elements
+            .borrow()
+            .get(&element_id)
+            .map_or(false, |element| {
+                matches!(&*element.borrow(), Element::Hidden(_) | Element::Comment(_))
+            })
+    }
 
-        // Set up the JS virtual machine
-        let vue_code = include_str!("../assets/vue.global.js");
-        isolate
-            .eval::<_, Value>(vue_code)
-            .expect("Failed to eval vue code");
+    // The children that take up a slot in layouts that place them by index
+    fn slotted_children(&self, element_id: ElementId) -> Vec<ElementId> {
+        self.elements_children
+            .borrow()
+            .get(&element_id)
+            .map(|children| {
+                children
+                    .iter()
+                    .copied()
+                    .filter(|child_id| !self.is_placeholder(*child_id))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 
-        // Try to initialize the Vue app
-        let vue_init_code = r#"
-try {
-    const { createRenderer, defineComponent, h, reactive } = Vue;
+    fn render_children(&self, ui: &mut egui::Ui, element_id: ElementId) -> Vec<Response> {
+        let mut responses = Vec::new();
+        let elements_children_borrow = self.elements_children.borrow();
+        let children = elements_children_borrow.get(&element_id);
+        if let Some(children) = children {
+            for child_id in children {
+                let local_responses = self.render_element(ui, *child_id);
+                responses.extend(local_responses);
+            }
+        }
+        responses
+    }
 
-    console.log('Vue version:', Vue.version);
-    console.log('Testing console.log');
-    console.log('Small array:', [1, 2, 3]);
-    console.log('Small object:', { a: 1 });
+    fn render_layout(
+        &self,
+        ui: &mut egui::Ui,
+        element_id: ElementId,
+        vertical: bool,
+        layout_options: LayoutOptions,
+    ) -> Vec<Response> {
+        let LayoutOptions {
+            align,
+            stretch,
+            justify,
+            spacing,
+            padding,
+        } = layout_options;
+        // Same defaults as `ui.vertical` and `ui.horizontal`
+        let cross = align.unwrap_or(if vertical {
+            egui::Align::Min
+        } else {
+            egui::Align::Center
+        });
+        // End justification flips the direction, the children are reversed to keep their order
+        let layout = match (vertical, justify) {
+            (true, egui::Align::Max) => egui::Layout::bottom_up(cross),
+            (true, _) => egui::Layout::top_down(cross),
+            (false, egui::Align::Max) => egui::Layout::right_to_left(cross),
+            (false, _) => egui::Layout::left_to_right(cross),
+        }
+        .with_cross_justify(stretch);
 
-    const elementToId = new Map();
-    const idToElement = new Map();
-    let nextId = 1;
-    function getId(element) {
-        if (!elementToId.has(element)) {
-            elementToId.set(element, nextId++);
+        let mut children = self
+            .elements_children
+            .borrow()
+            .get(&element_id)
+            .cloned()
+            .unwrap_or_default();
+        if justify == egui::Align::Max {
+            children.reverse();
         }
-        return elementToId.get(element);
-    }
-    function getElementById(id) {
-        return idToElement.get(id);
-    }
 
-    const nodeOps = {
-        // Create a node in the non-DOM environment
-        createElement(tag) {
-            const id = nextId++; 
-            let element = { id: createElement(id, tag) };
-            elementToId.set(element, id);
-            idToElement.set(id, element);
-            return element;
-        },
-        // Insert child into parent, possibly using some custom API
-        insert(child, parent, anchor) {
-            insertElement(child.id, parent.id, anchor?.id);
-        },
-        // Remove an element, adapting to your backend's capabilities
-        remove(child) {
-            removeElement(child.id);
-        },
-        createText(text) {
-            const id = nextId++;
-            let element = { id: createElement(id, 'hidden') };
-            setElementText(element.id, text);
-            return element;
-        },
-        createComment(text) {
-            const id = nextId++;
-            let element = { id: createElement(id, 'comment') };
-            setElementText(element.id, text);
-            return element;
-        },
-        setText(node, text) {
-            console.log('Setting text for node:', node);
-            setElementText(node.id, text);
-        },
-        setElementText(el, text) {
-            setElementText(el.id, text);
-        },
-        patchProp(el, key, prevValue, nextValue) {
-            patchProp(el.id, key, prevValue, nextValue);
-        },
-        parentNode(node) {
-            return getElementById(parentNode(node.id));
-        },
-        nextSibling(node) {
-            return getElementById(nextSibling(node.id));
-        },
-        querySelector(selector) {
-            throw new Error(`Not implemented, trying to query selector: ${selector}`);
-        },
-    };
+        let frame = egui::Frame::none().inner_margin(padding.unwrap_or(0.0));
+        let frame_response = frame.show(ui, |ui| {
+            let available = ui.available_size_before_wrap();
+            let size = match (vertical, justify) {
+                (true, egui::Align::Min) => egui::vec2(available.x, 0.0),
+                (true, _) => available,
+                (false, _) => egui::vec2(available.x, ui.spacing().interact_size.y),
+            };
+            ui.allocate_ui_with_layout(size, layout, |ui| {
+                if let Some(spacing) = spacing {
+                    ui.spacing_mut().item_spacing = spacing;
+                }
 
-    const { render, createApp } = createRenderer(nodeOps);
-    const { watch, ref } = Vue;
+                // Centering needs the content size, which is only known after the children
+                // were laid out, so the size measured in the previous frame is used
+                let main_size = |size: egui::Vec2| if vertical { size.y } else { size.x };
+                let centered_id = egui::Id::new(("justify_center", element_id));
+                let measured = ui.data(|data| data.get_temp::<f32>(centered_id));
+                if justify == egui::Align::Center {
+                    let free = main_size(ui.available_size_before_wrap()) - measured.unwrap_or(0.0);
+                    ui.add_space((free / 2.0).max(0.0));
+                }
 
-    const App = {
-        setup() {
-            console.log('App setup:');
+                let start = ui.cursor().min;
+                let mut responses = Vec::new();
+                for child_id in children {
+                    responses.extend(self.render_element(ui, child_id));
+                }
 
-            const value = ref(0);
-            const stringValue = ref('Hello, Vue!');
-            const additionalControls = ref(false);
+                if justify == egui::Align::Center {
+                    let content = main_size(ui.min_rect().max - start);
+                    if measured != Some(content) {
+                        ui.data_mut(|data| data.insert_temp(centered_id, content));
+                        ui.ctx().request_repaint();
+                    }
+                }
+                responses
+            })
+            .inner
+        });
+        frame_response.inner
+    }
 
-            const label = ref(null);
-            watch(label, (value) => {
-                console.log('Label changed:', value);
-            });
+    // Flattens the text nodes and spans below an element into a single layout job
+    fn append_rich_text(
+        &self,
+        ui: &egui::Ui,
+        job: &mut egui::text::LayoutJob,
+        element_id: ElementId,
+        text: &str,
+        span: TextSpan,
+    ) {
+        let append = |job: &mut egui::text::LayoutJob, text: &str, span: TextSpan| {
+            span.rich_text(text).append_to(
+                job,
+                ui.style(),
+                egui::FontSelection::Default,
+                egui::Align::Center,
+            );
+        };
+        if !text.is_empty() {
+            append(job, text, span);
+        }
 
-            return { value, additionalControls, label, stringValue };
-        },
-        template: `
-            <vertical>
-                <label ref="label">Value: {{ value }}</label>
-                <button @click="value++">Increment</button>
-                <button @click="value = 0">Reset</button>
-                <label>String Value: {{ stringValue }}</label>
+        let elements_borrow = self.elements.borrow();
+        let elements_children_borrow = self.elements_children.borrow();
+        let Some(children) = elements_children_borrow.get(&element_id) else {
+            return;
+        };
+        for child_id in children {
+            let child_ref = elements_borrow.get(child_id).expect("Failed to get child");
+            let child = child_ref.borrow();
+            match &*child {
+                Element::Hidden(text) => append(job, text, span),
+                Element::Span(inner, text) => {
+                    self.append_rich_text(ui, job, *child_id, text, span.merge(*inner));
+                }
+                _ => {}
+            }
+        }
+    }
 
-                // Additional controls
-                <button @click="additionalControls = !additionalControls">Toggle Controls</button>
-                <vertical v-if="additionalControls">
-                    <label>Additional Controls</label>
-                    <button @click="value--">Decrement</button>
-                    <text-edit @input="(v) => stringValue = v">{{ stringValue }}</text-edit>
-                </vertical>
-                <separator></separator>
-            </vertical>
-        `,
-    };
+    // Walking the tree with a stack of contexts
+    // Will be used later for rendering with eframe/egui
+    fn render_element(&self, ui: &mut egui::Ui, element_id: ElementId) -> Vec<Response> {
+        // Invisible elements keep their place in the tree (and their state), but are skipped
+        let hidden = self
+            .element_props
+            .borrow()
+            .get(&element_id)
+            .map_or(false, |props| props.hidden);
+        // Fragment anchors and `v-if` comments must not even take up item spacing
+        if hidden || self.is_placeholder(element_id) {
+            return Vec::new();
+        }
+        self.rendered_elements
+            .borrow_mut()
+            .current
+            .insert(element_id);
 
-    // The 'root' object would represent the top level of your app
-    const root = { id: 0 };
-    console.log(`Root object created:`, root);
-    const unmountedApp = createApp(App);
-    unmountedApp.config.isCustomElement = tag => {
-        console.log(`Checking if ${tag} is a custom element`);
-        return [
-            'label',
-            'vertical',
-            'horizontal',
-            'button',
-            'hidden',
-            'comment',
-            'separator',
-            'text-edit',
-        ].includes(tag);
-    };
-    const appInstance = unmountedApp.mount(root);
-} catch (e) {
-    const errorMessage = `Error Message: ${e.message}`;
-    const stackTrace = `Stack Trace:\n${e.stack}`;
+        let elements_borrow = self.elements.borrow();
+        let element_ref = elements_borrow
+            .get(&element_id)
+            .expect("Failed to get element");
+        let mut element = element_ref.borrow_mut();
+        let mut responses = Vec::new();
 
-    console.error(`${errorMessage}\n${stackTrace}`);
-}
-"#;
+        // Disabled elements are greyed out together with their children, and fire no events
+        let (disabled, draggable, sizing, style, text_wrap, icon) =
+            self.element_props.borrow().get(&element_id).map_or(
+                (
+                    false,
+                    false,
+                    Sizing::default(),
+                    None,
+                    TextWrap::default(),
+                    None,
+                ),
+                |props| {
+                    (
+                        props.disabled,
+                        props.draggable && !props.disabled,
+                        props.sizing,
+                        self.styles.resolve(&props.classes, &props.scope_ids),
+                        props.text_wrap,
+                        props.icon.clone(),
+                    )
+                },
+            );
+        // While dragged, the element is painted on a floating layer that follows the pointer,
+        // its place in the layout stays reserved
+        let drag_id = egui::Id::new(("drag_source", element_id));
+        let drag_layer = egui::LayerId::new(egui::Order::Tooltip, drag_id);
+        let dragging = draggable && ui.ctx().is_being_dragged(drag_id);
+        // Transitioning elements fade, and slide on their own layer
+        let animation = self
+            .animations
+            .borrow()
+            .get(&element_id)
+            .map(|animation| (animation.opacity(), animation.translation()));
+        let animation_layer = egui::LayerId::new(
+            ui.layer_id().order,
+            egui::Id::new(("animation", element_id)),
+        );
+        let sliding = animation.is_some_and(|(_, translation)| translation != egui::Vec2::ZERO);
+        let layer_id = if dragging {
+            drag_layer
+        } else if sliding {
+            animation_layer
+        } else {
+            ui.layer_id()
+        };
+        let rendered = ui.with_layer_id(layer_id, |ui| {
+            ui.set_enabled(!disabled);
+            if let Some((opacity, _)) = animation {
+                ui.set_opacity(opacity);
+            }
+            sizing.apply(ui);
+            if let Some(style) = style {
+                style.apply(ui);
+            }
+            match &mut *element {
+                Element::Root => {
+                    responses.extend(self.render_children(ui, element_id));
+                }
+                Element::Label(label) => {
+                    let has_children = self
+                        .elements_children
+                        .borrow()
+                        .get(&element_id)
+                        .map_or(false, |children| !children.is_empty());
+                    let label = icons::prepend(icon.as_deref(), label);
+                    let text: egui::WidgetText = if has_children {
+                        let mut job = egui::text::LayoutJob::default();
+                        self.append_rich_text(
+                            ui,
+                            &mut job,
+                            element_id,
+                            &label,
+                            TextSpan::default(),
+                        );
+                        job.into()
+                    } else {
+                        label.into()
+                    };
+                    let (text, wrap) = text_wrap.apply(ui, text, egui::TextStyle::Body);
+                    let mut widget = egui::Label::new(text);
+                    if let Some(wrap) = wrap {
+                        widget = widget.wrap(wrap);
+                    }
+                    responses.push(ui.add(widget));
+                }
+                Element::Span(span, text) => {
+                    let mut job = egui::text::LayoutJob::default();
+                    self.append_rich_text(ui, &mut job, element_id, text, *span);
+                    responses.push(ui.label(job));
+                }
+                Element::SelectableLabel { text, selection } => {
+                    // A frameless read-only text edit, unlike a label it exposes the selected range
+                    let mut text_ref = text.as_str();
+                    let output = egui::TextEdit::multiline(&mut text_ref)
+                        .frame(false)
+                        .desired_rows(1)
+                        .desired_width(ui.available_width())
+                        .show(ui);
+                    let selected: String = output
+                        .cursor_range
+                        .map(|cursor_range| {
+                            let range = cursor_range.as_sorted_char_range();
+                            text.chars()
+                                .skip(range.start)
+                                .take(range.end - range.start)
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    responses.push(output.response);
 
-        isolate
-            .eval::<_, Value>(vue_init_code)
-            .map_err(|e| eyre::eyre!(format!("MiniV8 error: {:#?}", e)))?;
+                    if *selection != selected {
+                        *selection = selected;
+                        let element_events_borrow = self.element_events.borrow();
+                        let select = element_events_borrow
+                            .get(&element_id)
+                            .and_then(|events| events.select.as_ref());
+                        if let Some(select) = select {
+                            let selection = selection
+                                .clone()
+                                .to_value(&self.isolate)
+                                .expect("Failed to convert selection");
+                            call_handler(&self.isolate, select, "select", vec![selection]);
+                        }
+                    }
+                }
+                Element::Button(label) => {
+                    let label = icons::prepend(icon.as_deref(), label);
+                    let (text, wrap) = text_wrap.apply(ui, label, egui::TextStyle::Button);
+                    let mut button = egui::Button::new(text).min_size(sizing.min_size());
+                    if let Some(wrap) = wrap {
+                        button = button.wrap(wrap);
+                    }
+                    responses.push(ui.add(button));
+                }
+                Element::Hidden(_) => { /* do nothing */ }
+                Element::Shortcut(_) => { /* checked in run_shortcuts */ }
+                Element::Comment(_) => { /* do nothing */ }
+                Element::Vertical(layout_options) => {
+                    responses.extend(self.render_layout(ui, element_id, true, *layout_options));
+                }
+                Element::Horizontal(layout_options) => {
+                    responses.extend(self.render_layout(ui, element_id, false, *layout_options));
+                }
+                Element::Wrap => {
+                    ui.horizontal_wrapped(|ui| {
+                        responses.extend(self.render_children(ui, element_id));
+                    });
+                }
+                Element::Scroll {
+                    horizontal,
+                    vertical,
+                    max_height,
+                    offset,
+                } => {
+                    let mut scroll_area = egui::ScrollArea::new([*horizontal, *vertical])
+                        .id_source(("scroll", element_id));
+                    if let Some(max_height) = max_height.or(sizing.height) {
+                        scroll_area = scroll_area.max_height(max_height);
+                    }
+                    let scroll_output =
+                        scroll_area.show(ui, |ui| self.render_children(ui, element_id));
+                    responses.extend(scroll_output.inner);
+
+                    let new_offset = scroll_output.state.offset;
+                    if new_offset != *offset {
+                        *offset = new_offset;
+                        let element_events_borrow = self.element_events.borrow();
+                        let scroll_event = element_events_borrow
+                            .get(&element_id)
+                            .and_then(|events| events.scroll.as_ref());
+                        if let Some(scroll_event) = scroll_event {
+                            // the furthest offset, so scripts can tell when the end is near
+                            let max = (scroll_output.content_size
+                                - scroll_output.inner_rect.size())
+                            .max(egui::Vec2::ZERO);
+                            let max_object = self.isolate.create_object();
+                            max_object.set("x", max.x).expect("Failed to set max x");
+                            max_object.set("y", max.y).expect("Failed to set max y");
+                            // x and y are the scroll offset, not the pointer position
+                            let event = EventBuilder::new(&self.isolate, "scroll", element_id)
+                                .input(ui.ctx())
+                                .field("x", new_offset.x)
+                                .field("y", new_offset.y)
+                                .field("max", max_object)
+                                .build();
+                            call_handler(&self.isolate, scroll_event, "scroll", vec![event]);
+                        }
+                    }
+                }
+                Element::Columns(count) => {
+                    // Children are distributed over the columns in order, wrapping around
+                    let children = self.slotted_children(element_id);
+                    let count = count.unwrap_or(children.len()).max(1);
+                    ui.columns(count, |columns| {
+                        for (index, child_id) in children.into_iter().enumerate() {
+                            let column = &mut columns[index % count];
+                            responses.extend(self.render_element(column, child_id));
+                        }
+                    });
+                }
+                Element::Separator => {
+                    ui.separator();
+                }
+                Element::TextEdit {
+                    text,
+                    password,
+                    revealable,
+                    placeholder,
+                } => {
+                    let text_edit_id = egui::Id::new(("text_edit", element_id));
+                    let focused = ui.memory(|memory| memory.has_focus(text_edit_id));
+                    if focused && !self.is_root_child(element_id) {
+                        self.intercept_paste(ui.ctx(), element_id);
+                    }
+                    if *password && *revealable {
+                        // Reveal the password while the eye button is held down
+                        ui.horizontal(|ui| {
+                            let reveal_id = egui::Id::new(("reveal", element_id));
+                            let revealed = ui.data(|data| data.get_temp::<bool>(reveal_id));
+                            let mut text_edit = egui::TextEdit::singleline(text)
+                                .id(text_edit_id)
+                                .password(!revealed.unwrap_or(false))
+                                .hint_text(placeholder.clone());
+                            if let Some(width) = sizing.width {
+                                text_edit = text_edit.desired_width(width);
+                            }
+                            responses.push(ui.add(text_edit));
+                            let reveal = ui.button("👁");
+                            let held = reveal.is_pointer_button_down_on();
+                            ui.data_mut(|data| data.insert_temp(reveal_id, held));
+                        });
+                    } else {
+                        let mut text_edit = egui::TextEdit::singleline(text)
+                            .id(text_edit_id)
+                            .password(*password)
+                            .hint_text(placeholder.clone());
+                        if let Some(width) = sizing.width {
+                            text_edit = text_edit.desired_width(width);
+                        }
+                        responses.push(ui.add(text_edit));
+                    }
+                }
+                Element::TextArea {
+                    text,
+                    rows,
+                    desired_width,
+                    placeholder,
+                    auto_grow,
+                } => {
+                    let text_edit_id = egui::Id::new(("text_edit", element_id));
+                    let focused = ui.memory(|memory| memory.has_focus(text_edit_id));
+                    if focused && !self.is_root_child(element_id) {
+                        self.intercept_paste(ui.ctx(), element_id);
+                    }
+                    let mut text_edit = egui::TextEdit::multiline(text)
+                        .id(text_edit_id)
+                        .desired_rows(*rows)
+                        .hint_text(placeholder.clone());
+                    if let Some(desired_width) = desired_width.or(sizing.width) {
+                        text_edit = text_edit.desired_width(desired_width);
+                    }
+                    if *auto_grow {
+                        responses.push(ui.add(text_edit));
+                    } else {
+                        // Keep the height at `rows` and scroll the overflowing content instead
+                        let row_height = ui.text_style_height(&egui::TextStyle::Body);
+                        let margin = ui.spacing().button_padding.y * 2.0;
+                        let scroll_area = egui::ScrollArea::vertical()
+                            .id_source(("text_area", element_id))
+                            .max_height(row_height * *rows as f32 + margin);
+                        let scroll_response = scroll_area.show(ui, |ui| ui.add(text_edit));
+                        responses.push(scroll_response.inner);
+                    }
+                }
+                Element::Checkbox(label, checked) => {
+                    responses.push(ui.checkbox(checked, label.clone()));
+                }
+                Element::Slider {
+                    text,
+                    value,
+                    min,
+                    max,
+                    step,
+                    format,
+                } => {
+                    let mut slider = egui::Slider::new(value, *min..=*max)
+                        .text(text.clone())
+                        .prefix(format.prefix.clone())
+                        .suffix(format.suffix.clone())
+                        .logarithmic(format.logarithmic);
+                    if let Some(step) = step {
+                        slider = slider.step_by(*step);
+                    }
+                    if let Some(precision) = format.precision {
+                        slider = slider.fixed_decimals(precision);
+                    }
+                    responses.push(ui.add(slider));
+                }
+                Element::Number {
+                    value,
+                    min,
+                    max,
+                    speed,
+                    format,
+                } => {
+                    // Drag values have no logarithmic mode, `logarithmic` only affects sliders
+                    let mut drag_value = egui::DragValue::new(value)
+                        .speed(*speed)
+                        .prefix(format.prefix.clone())
+                        .suffix(format.suffix.clone());
+                    if let Some(precision) = format.precision {
+                        drag_value = drag_value.fixed_decimals(precision);
+                    }
+                    if min.is_some() || max.is_some() {
+                        let min = min.unwrap_or(f64::NEG_INFINITY);
+                        let max = max.unwrap_or(f64::INFINITY);
+                        drag_value = drag_value.clamp_range(min..=max);
+                    }
+                    responses.push(ui.add(drag_value));
+                }
+                Element::Spinner(size) => {
+                    let mut spinner = egui::Spinner::new();
+                    if let Some(size) = size {
+                        spinner = spinner.size(*size);
+                    }
+                    responses.push(ui.add(spinner));
+                }
+                Element::MenuBar => { /* rendered in the top panel, see `render_menu_bars` */ }
+                Element::Menu(title) => {
+                    let menu =
+                        ui.menu_button(title.clone(), |ui| self.render_children(ui, element_id));
+                    responses.push(menu.response);
+                    if let Some(inner_responses) = menu.inner {
+                        responses.extend(inner_responses);
+                    }
+                }
+                Element::MenuItem(label) => {
+                    let response = ui.button(label.clone());
+                    if response.clicked() {
+                        ui.close_menu();
+                    }
+                    responses.push(response);
+                }
+                Element::ContextMenu => { /* attached to the parent's widget, see below */ }
+                Element::Plot { series, height } => {
+                    let mut plot = egui_plot::Plot::new(("plot", element_id))
+                        .legend(egui_plot::Legend::default());
+                    if let Some(height) = height {
+                        plot = plot.height(*height);
+                    }
+                    let plot_response = plot.show(ui, |plot_ui| {
+                        for series in series.iter() {
+                            let mut line = egui_plot::Line::new(egui_plot::PlotPoints::from(
+                                series.points.clone(),
+                            ));
+                            if let Some(name) = &series.name {
+                                line = line.name(name);
+                            }
+                            if let Some(color) = series.color {
+                                line = line.color(color);
+                            }
+                            plot_ui.line(line);
+                        }
+                    });
+                    responses.push(plot_response.response);
+                }
+                Element::Frame {
+                    fill,
+                    stroke_color,
+                    stroke_width,
+                    rounding,
+                    inner_margin,
+                    outer_margin,
+                    spacing,
+                } => {
+                    let mut frame = egui::Frame::group(ui.style());
+                    if let Some(fill) = fill {
+                        frame = frame.fill(*fill);
+                    }
+                    if let Some(stroke_color) = stroke_color {
+                        frame.stroke.color = *stroke_color;
+                    }
+                    if let Some(stroke_width) = stroke_width {
+                        frame.stroke.width = *stroke_width;
+                    }
+                    if let Some(rounding) = rounding {
+                        frame = frame.rounding(*rounding);
+                    }
+                    if let Some(inner_margin) = inner_margin {
+                        frame = frame.inner_margin(*inner_margin);
+                    }
+                    if let Some(outer_margin) = outer_margin {
+                        frame = frame.outer_margin(*outer_margin);
+                    }
+                    let frame_response = frame.show(ui, |ui| {
+                        if let Some(spacing) = spacing {
+                            ui.spacing_mut().item_spacing = *spacing;
+                        }
+                        self.render_children(ui, element_id)
+                    });
+                    responses.push(frame_response.response);
+                    responses.extend(frame_response.inner);
+                }
+                Element::Panel { .. } => { /* shown before the central panel */ }
+                Element::Markdown(source) => {
+                    let mut markdown_cache = self.markdown_cache.borrow_mut();
+                    egui_commonmark::CommonMarkViewer::new(("markdown", element_id)).show(
+                        ui,
+                        &mut markdown_cache,
+                        source,
+                    );
+                }
+                Element::Spacer {
+                    width,
+                    height,
+                    grow,
+                } => {
+                    let horizontal = ui.layout().main_dir().is_horizontal();
+                    if let (false, Some(width), Some(height)) = (*grow, width, height) {
+                        ui.allocate_space(egui::vec2(*width, *height));
+                    } else {
+                        let size = if *grow {
+                            // take up the rest of the space along the layout direction
+                            let available = ui.available_size_before_wrap();
+                            Some(if horizontal { available.x } else { available.y })
+                        } else if horizontal {
+                            *width
+                        } else {
+                            *height
+                        };
+                        let spacing = ui.spacing().item_spacing;
+                        let default_size = if horizontal { spacing.x } else { spacing.y };
+                        ui.add_space(size.unwrap_or(default_size));
+                    }
+                }
+                Element::Split { ratio, vertical } => {
+                    const DIVIDER_SIZE: f32 = 6.0;
+                    let rect = ui.available_rect_before_wrap();
+                    let along = |rect: egui::Rect| {
+                        if *vertical {
+                            rect.height()
+                        } else {
+                            rect.width()
+                        }
+                    };
+                    let available = (along(rect) - DIVIDER_SIZE).max(0.0);
 
-        let this = Self {
-            isolate,
-            elements,
-            elements_children,
-            element_events,
-        };
-        this.print_tree(0, 0);
-        Ok(this)
-    }
+                    // Drag the divider to change the ratio
+                    let divider_rect = if *vertical {
+                        let top = rect.top() + available * *ratio;
+                        egui::Rect::from_x_y_ranges(rect.x_range(), top..=top + DIVIDER_SIZE)
+                    } else {
+                        let left = rect.left() + available * *ratio;
+                        egui::Rect::from_x_y_ranges(left..=left + DIVIDER_SIZE, rect.y_range())
+                    };
+                    let mut divider = ui.interact(
+                        divider_rect,
+                        egui::Id::new(("split", element_id)),
+                        egui::Sense::drag(),
+                    );
+                    if let (true, Some(pointer)) =
+                        (divider.dragged(), divider.interact_pointer_pos())
+                    {
+                        let offset = if *vertical {
+                            pointer.y - rect.top()
+                        } else {
+                            pointer.x - rect.left()
+                        };
+                        let new_ratio = (offset / available.max(1.0)).clamp(0.05, 0.95);
+                        if new_ratio != *ratio {
+                            *ratio = new_ratio;
+                            divider.mark_changed();
+                        }
+                    }
+                    let divider = divider.on_hover_cursor(if *vertical {
+                        egui::CursorIcon::ResizeVertical
+                    } else {
+                        egui::CursorIcon::ResizeHorizontal
+                    });
+                    let stroke = ui.style().interact(&divider).bg_stroke;
 
-    // Element related functions
-    fn print_tree(&self, element_id: ElementId, depth: usize) {
-        let indent = "  ".repeat(depth);
-        let elements_borrow = self.elements.borrow();
-        let element_ref = elements_borrow
-            .get(&element_id)
-            .expect("Failed to get element");
-        let element = element_ref.borrow();
-        match &*element {
-            Element::Root => {
-                println!("{}Root({})", indent, element_id);
-            }
-            Element::Label(label) => {
-                println!("{}Label({}): {}", indent, element_id, label);
-            }
-            Element::Button(label) => {
-                println!("{}Button({}): {}", indent, element_id, label);
-            }
-            Element::Vertical => {
-                println!("{}Vertical({})", indent, element_id);
-            }
-            Element::Horizontal => {
-                println!("{}Horizontal({})", indent, element_id);
-            }
-            Element::Hidden(label) => {
-                println!("{}Hidden({}): {}", indent, element_id, label);
-            }
-            Element::Comment(comment) => {
-                println!("{}Comment({}): {}", indent, element_id, comment);
-            }
-            Element::Separator => {
-                println!("{}Separator({})", indent, element_id);
-            }
-            Element::TextEdit(label) => {
-                println!("{}TextEdit({}): {}", indent, element_id, label);
-            }
-        }
+                    // Lay out the two slots around the divider
+                    let first_size = available * *ratio;
+                    let (first_rect, divider_rect, second_rect) = if *vertical {
+                        let divider_top = rect.top() + first_size;
+                        (
+                            egui::Rect::from_x_y_ranges(rect.x_range(), rect.top()..=divider_top),
+                            egui::Rect::from_x_y_ranges(
+                                rect.x_range(),
+                                divider_top..=divider_top + DIVIDER_SIZE,
+                            ),
+                            egui::Rect::from_x_y_ranges(
+                                rect.x_range(),
+                                divider_top + DIVIDER_SIZE..=rect.bottom(),
+                            ),
+                        )
+                    } else {
+                        let divider_left = rect.left() + first_size;
+                        (
+                            egui::Rect::from_x_y_ranges(rect.left()..=divider_left, rect.y_range()),
+                            egui::Rect::from_x_y_ranges(
+                                divider_left..=divider_left + DIVIDER_SIZE,
+                                rect.y_range(),
+                            ),
+                            egui::Rect::from_x_y_ranges(
+                                divider_left + DIVIDER_SIZE..=rect.right(),
+                                rect.y_range(),
+                            ),
+                        )
+                    };
+                    if *vertical {
+                        ui.painter()
+                            .hline(divider_rect.x_range(), divider_rect.center().y, stroke);
+                    } else {
+                        ui.painter()
+                            .vline(divider_rect.center().x, divider_rect.y_range(), stroke);
+                    }
 
-        let elements_children_borrow = self.elements_children.borrow();
-        let children = elements_children_borrow.get(&element_id);
-        if let Some(children) = children {
-            for child_id in children {
-                self.print_tree(*child_id, depth + 1);
-            }
-        }
+                    let children = self.slotted_children(element_id);
+                    for (child_id, slot_rect) in children.into_iter().zip([first_rect, second_rect])
+                    {
+                        let mut slot_ui = ui.child_ui(slot_rect, *ui.layout());
+                        slot_ui.set_clip_rect(slot_rect.intersect(ui.clip_rect()));
+                        responses.extend(self.render_element(&mut slot_ui, child_id));
+                    }
+                    ui.allocate_rect(rect, egui::Sense::hover());
+                    responses.push(divider);
+                }
+                Element::Svg { src, .. } if src.is_empty() => { /* do nothing */ }
+                Element::Svg { src, width, height } => {
+                    let texture = self
+                        .svg_cache
+                        .borrow_mut()
+                        .get(ui.ctx(), src, *width, *height);
+                    match texture {
+                        Ok(texture) => {
+                            let size = texture.size_vec2() / ui.ctx().pixels_per_point();
+                            responses.push(ui.add(egui::Image::new((texture.id(), size))));
+                        }
+                        Err(error) => responses.push(ui.colored_label(egui::Color32::RED, error)),
+                    }
+                }
+                Element::Viewport3d {
+                    renderer,
+                    size,
+                    camera,
+                } => {
+                    let (response, painter) =
+                        ui.allocate_painter(*size, egui::Sense::click_and_drag());
+                    if camera.update(ui, &response) {
+                        let element_events_borrow = self.element_events.borrow();
+                        let camera_event = element_events_borrow
+                            .get(&element_id)
+                            .and_then(|events| events.camera.as_ref());
+                        if let Some(camera_event) = camera_event {
+                            let object = self.isolate.create_object();
+                            object.set("yaw", camera.yaw).expect("Failed to set yaw");
+                            object
+                                .set("pitch", camera.pitch)
+                                .expect("Failed to set pitch");
+                            object
+                                .set("distance", camera.distance)
+                                .expect("Failed to set distance");
+                            call_handler(
+                                &self.isolate,
+                                camera_event,
+                                "camera",
+                                vec![Value::Object(object)],
+                            );
+                        }
+                    }
 
-        println!("{}End", indent);
-    }
+                    painter.rect_filled(response.rect, 0.0, ui.visuals().extreme_bg_color);
+                    if self
+                        .viewports
+                        .paint(&painter, response.rect, renderer, *camera)
+                        .is_none()
+                    {
+                        painter.text(
+                            response.rect.center(),
+                            egui::Align2::CENTER_CENTER,
+                            format!("Unknown renderer: {}", renderer),
+                            egui::FontId::default(),
+                            ui.visuals().error_fg_color,
+                        );
+                    }
+                    responses.push(response);
+                }
+                Element::Sparkline {
+                    points,
+                    size,
+                    color,
+                } => {
+                    let (response, painter) = ui.allocate_painter(*size, egui::Sense::hover());
+                    let rect = response.rect.shrink(1.0);
+                    let color = color.unwrap_or(ui.visuals().selection.bg_fill);
 
-    // Walking the tree with a stack of contexts
-    // Will be used later for rendering with eframe/egui
-    fn render_element(&self, ui: &mut egui::Ui, element_id: ElementId) -> Vec<Response> {
-        let elements_borrow = self.elements.borrow();
-        let element_ref = elements_borrow
-            .get(&element_id)
-            .expect("Failed to get element");
-        let mut element = element_ref.borrow_mut();
-        let mut responses = Vec::new();
+                    // Points are stretched over the whole rect, a flat series sits in the middle
+                    let bounds =
+                        points
+                            .iter()
+                            .fold(egui::Rect::NOTHING, |bounds: egui::Rect, [x, y]| {
+                                bounds.union(egui::Rect::from_min_max(
+                                    egui::pos2(*x as f32, *y as f32),
+                                    egui::pos2(*x as f32, *y as f32),
+                                ))
+                            });
+                    let normalize = |value: f32, min: f32, max: f32| {
+                        if max > min {
+                            (value - min) / (max - min)
+                        } else {
+                            0.5
+                        }
+                    };
+                    let line: Vec<egui::Pos2> = points
+                        .iter()
+                        .map(|[x, y]| {
+                            let x = normalize(*x as f32, bounds.min.x, bounds.max.x);
+                            let y = normalize(*y as f32, bounds.min.y, bounds.max.y);
+                            egui::pos2(
+                                rect.left() + x * rect.width(),
+                                rect.bottom() - y * rect.height(),
+                            )
+                        })
+                        .collect();
+                    if let Some(last) = line.last().copied() {
+                        painter.add(egui::Shape::line(line, egui::Stroke::new(1.5, color)));
+                        painter.circle_filled(last, 2.0, color);
+                    }
+                    responses.push(response);
+                }
+                Element::Custom(tag) => {
+                    // Rendered from scratch every frame, the element's attributes are its props
+                    let props = self.isolate.create_object();
+                    if let Some(element_props) = self.element_props.borrow().get(&element_id) {
+                        for (key, value) in &element_props.attributes {
+                            props
+                                .set(key.as_str(), value.clone())
+                                .expect("Failed to set custom element prop");
+                        }
+                    }
+                    let children = self
+                        .elements_children
+                        .borrow()
+                        .get(&element_id)
+                        .cloned()
+                        .unwrap_or_default();
+                    match self
+                        .custom_elements
+                        .record(&self.isolate, tag, props, children)
+                    {
+                        Ok(commands) => {
+                            let inner = ui
+                                .vertical(|ui| self.render_ui_commands(ui, element_id, &commands));
+                            responses.push(inner.response.interact(egui::Sense::click()));
+                            responses.extend(inner.inner);
+                        }
+                        Err(error) => report_error(&self.isolate, "render", error),
+                    }
+                }
+                Element::Gauge {
+                    value,
+                    min,
+                    max,
+                    size,
+                    full,
+                    text,
+                } => {
+                    let (response, painter) =
+                        ui.allocate_painter(egui::Vec2::splat(*size), egui::Sense::hover());
+                    let visuals = ui.visuals();
+                    let rect = response.rect;
+                    let stroke_width = (*size * 0.1).max(2.0);
+                    let radius = (*size - stroke_width) / 2.0;
+                    let fraction = if *max > *min {
+                        ((*value - *min) / (*max - *min)).clamp(0.0, 1.0) as f32
+                    } else {
+                        0.0
+                    };
 
-        match &mut *element {
-            Element::Root => {
-                let elements_children_borrow = self.elements_children.borrow();
-                let children = elements_children_borrow.get(&element_id);
-                if let Some(children) = children {
-                    for child_id in children {
-                        let local_responses = self.render_element(ui, *child_id);
-                        responses.extend(local_responses);
+                    // A full circle starts at the top, an arc is open at the bottom
+                    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+                    let (start, sweep) = if *full {
+                        (-FRAC_PI_2, TAU)
+                    } else {
+                        (0.75 * PI, 1.5 * PI)
+                    };
+                    let arc = |from: f32, to: f32| -> Vec<egui::Pos2> {
+                        let segments = ((to - from).abs() / TAU * 64.0).ceil().max(1.0) as usize;
+                        (0..=segments)
+                            .map(|i| {
+                                let angle = from + (to - from) * i as f32 / segments as f32;
+                                rect.center() + radius * egui::vec2(angle.cos(), angle.sin())
+                            })
+                            .collect()
+                    };
+                    painter.add(egui::Shape::line(
+                        arc(start, start + sweep),
+                        egui::Stroke::new(stroke_width, visuals.extreme_bg_color),
+                    ));
+                    if fraction > 0.0 {
+                        painter.add(egui::Shape::line(
+                            arc(start, start + sweep * fraction),
+                            egui::Stroke::new(stroke_width, visuals.selection.bg_fill),
+                        ));
                     }
+                    let label = if text.is_empty() {
+                        format!("{}", value)
+                    } else {
+                        text.clone()
+                    };
+                    painter.text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        label,
+                        egui::FontId::proportional(*size * 0.2),
+                        visuals.text_color(),
+                    );
+                    responses.push(response);
                 }
-            }
-            Element::Label(label) => responses.push(ui.label(label.clone())),
-            Element::Button(label) => responses.push(ui.button(label.clone())),
-            Element::Hidden(_) => { /* do nothing */ }
-            Element::Comment(_) => { /* do nothing */ }
-            Element::Vertical => {
-                ui.vertical(|ui| {
+                Element::Segmented { selected, options } => {
+                    ui.horizontal(|ui| {
+                        // no gaps between the segments, so they read as a single control
+                        ui.spacing_mut().item_spacing.x = 0.0;
+                        for option in options.iter() {
+                            responses.push(ui.selectable_value(
+                                selected,
+                                option.value.clone(),
+                                option.label.clone(),
+                            ));
+                        }
+                    });
+                }
+                Element::DropZone => {
+                    let frame_input = self.frame_input.borrow();
+                    let mut frame = egui::Frame::group(ui.style());
+                    if frame_input.hovered_files > 0 {
+                        frame.stroke = ui.visuals().selection.stroke;
+                        frame = frame.fill(ui.visuals().selection.bg_fill.gamma_multiply(0.25));
+                    }
+                    let frame_response = frame.show(ui, |ui| self.render_children(ui, element_id));
+                    let rect = frame_response.response.rect;
+                    responses.push(frame_response.response);
+                    responses.extend(frame_response.inner);
+
+                    // Files dropped onto the zone, or anywhere if the pointer position is unknown
+                    let pointer = ui.ctx().input(|i| i.pointer.latest_pos());
+                    if !frame_input.dropped_files.is_empty()
+                        && pointer.map_or(true, |pointer| rect.contains(pointer))
+                    {
+                        let element_events_borrow = self.element_events.borrow();
+                        let drop = element_events_borrow
+                            .get(&element_id)
+                            .and_then(|events| events.drop.as_ref());
+                        if let Some(drop) = drop {
+                            let paths = frame_input
+                                .dropped_files
+                                .clone()
+                                .to_value(&self.isolate)
+                                .expect("Failed to convert dropped files");
+                            call_handler(&self.isolate, drop, "drop", vec![paths]);
+                        }
+                    }
+                }
+                Element::Canvas(size) => {
+                    let (response, painter) =
+                        ui.allocate_painter(*size, egui::Sense::click_and_drag());
+                    let element_events_borrow = self.element_events.borrow();
+                    let draw = element_events_borrow
+                        .get(&element_id)
+                        .and_then(|events| events.draw.as_ref());
+                    if let Some(draw) = draw {
+                        match self.canvas.record(draw, response.rect.size()) {
+                            Ok(commands) => canvas::paint(&painter, response.rect.min, &commands),
+                            Err(error) => report_error(&self.isolate, "draw", error),
+                        }
+                    }
+                    responses.push(response);
+                }
+                Element::RadioGroup(selected) => {
+                    // Radio children are rendered by the group, so they can share its selected value
                     let elements_children_borrow = self.elements_children.borrow();
                     let children = elements_children_borrow.get(&element_id);
                     if let Some(children) = children {
                         for child_id in children {
-                            let local_responses = self.render_element(ui, *child_id);
-                            responses.extend(local_responses);
+                            let child_ref =
+                                elements_borrow.get(child_id).expect("Failed to get child");
+                            let radio = match &*child_ref.borrow() {
+                                Element::Radio { text, value } => {
+                                    Some((text.clone(), value.clone()))
+                                }
+                                _ => None,
+                            };
+                            if let Some((text, value)) = radio {
+                                responses.push(ui.radio_value(selected, value, text));
+                            } else {
+                                let local_responses = self.render_element(ui, *child_id);
+                                responses.extend(local_responses);
+                            }
                         }
                     }
-                });
-            }
-            Element::Horizontal => {
-                ui.horizontal(|ui| {
+                }
+                Element::Radio { text, .. } => {
+                    responses.push(ui.radio(false, text.clone()));
+                }
+                Element::Progress { value, text } => {
+                    let mut progress = egui::ProgressBar::new(*value);
+                    if let Some(text) = text {
+                        progress = progress.text(text.clone());
+                    }
+                    responses.push(ui.add(progress));
+                }
+                Element::Image(src) if src.is_empty() => { /* do nothing */ }
+                Element::Image(src) => match self.textures.get(ui.ctx(), src) {
+                    TextureStatus::Ready(texture) => {
+                        let image = egui::Image::new((texture.id(), texture.size_vec2()));
+                        responses.push(ui.add(image));
+                    }
+                    TextureStatus::Loading => responses.push(ui.spinner()),
+                    TextureStatus::Failed(error) => {
+                        responses.push(ui.colored_label(egui::Color32::RED, error))
+                    }
+                },
+                Element::ImageButton {
+                    src,
+                    width,
+                    height,
+                    selected,
+                } => match self.textures.get(ui.ctx(), src) {
+                    TextureStatus::Ready(texture) => {
+                        // A single given dimension scales the other one to keep the aspect ratio
+                        let texture_size = texture.size_vec2();
+                        let size = match (width, height) {
+                            (Some(width), Some(height)) => egui::vec2(*width, *height),
+                            (Some(width), None) => texture_size * (*width / texture_size.x),
+                            (None, Some(height)) => texture_size * (*height / texture_size.y),
+                            (None, None) => texture_size,
+                        };
+                        let image = egui::Image::new((texture.id(), size));
+                        responses.push(ui.add(egui::ImageButton::new(image).selected(*selected)));
+                    }
+                    TextureStatus::Loading => responses.push(ui.spinner()),
+                    TextureStatus::Failed(error) => {
+                        responses.push(ui.colored_label(egui::Color32::RED, error))
+                    }
+                },
+                Element::Link { text, href } => {
+                    // An onClick handler overrides navigation, so the app can route the link itself
+                    let intercepted = self
+                        .element_events
+                        .borrow()
+                        .get(&element_id)
+                        .map_or(false, |events| events.click.is_some());
+                    if intercepted {
+                        responses.push(ui.link(text.clone()).on_hover_text(href.clone()));
+                    } else {
+                        responses.push(ui.hyperlink_to(text.clone(), href.clone()));
+                    }
+                }
+                Element::Collapsing {
+                    title,
+                    default_open,
+                } => {
+                    let collapsing = egui::CollapsingHeader::new(title.clone())
+                        .id_source(element_id)
+                        .default_open(*default_open)
+                        .show(ui, |ui| self.render_children(ui, element_id));
+                    responses.push(collapsing.header_response);
+                    if let Some(body_responses) = collapsing.body_returned {
+                        responses.extend(body_responses);
+                    }
+                }
+                Element::Grid {
+                    columns,
+                    spacing,
+                    striped,
+                } => {
+                    let mut grid = egui::Grid::new(("grid", element_id)).striped(*striped);
+                    if let Some(columns) = columns {
+                        grid = grid.num_columns(*columns);
+                    }
+                    if let Some(spacing) = spacing {
+                        grid = grid.spacing(*spacing);
+                    }
+                    grid.show(ui, |ui| {
+                        // Every row child becomes a grid row, its children being the cells
+                        let elements_children_borrow = self.elements_children.borrow();
+                        let children = elements_children_borrow.get(&element_id);
+                        if let Some(children) = children {
+                            for child_id in children {
+                                let child_ref =
+                                    elements_borrow.get(child_id).expect("Failed to get child");
+                                let is_row = matches!(&*child_ref.borrow(), Element::Row);
+                                if is_row {
+                                    responses.extend(self.render_children(ui, *child_id));
+                                    ui.end_row();
+                                } else {
+                                    responses.extend(self.render_element(ui, *child_id));
+                                }
+                            }
+                        }
+                    });
+                }
+                Element::Row => {
+                    ui.horizontal(|ui| {
+                        responses.extend(self.render_children(ui, element_id));
+                    });
+                }
+                Element::Tabs(active) => {
                     let elements_children_borrow = self.elements_children.borrow();
-                    let children = elements_children_borrow.get(&element_id);
-                    if let Some(children) = children {
-                        for child_id in children {
-                            let local_responses = self.render_element(ui, *child_id);
-                            responses.extend(local_responses);
+                    let tabs: Vec<(ElementId, String)> = elements_children_borrow
+                        .get(&element_id)
+                        .map(|children| {
+                            children
+                                .iter()
+                                .filter_map(|child_id| {
+                                    let child_ref =
+                                        elements_borrow.get(child_id).expect("Failed to get child");
+                                    match &*child_ref.borrow() {
+                                        Element::Tab(title) => Some((*child_id, title.clone())),
+                                        _ => None,
+                                    }
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
+                    ui.horizontal(|ui| {
+                        for (index, (_, title)) in tabs.iter().enumerate() {
+                            responses.push(ui.selectable_value(active, index, title.clone()));
                         }
+                    });
+                    ui.separator();
+
+                    // Only the active tab's content is rendered
+                    if let Some((tab_id, _)) = tabs.get(*active) {
+                        responses.extend(self.render_children(ui, *tab_id));
                     }
-                });
+                }
+                Element::Tab(_) => {
+                    responses.extend(self.render_children(ui, element_id));
+                }
+                Element::Table {
+                    columns,
+                    striped,
+                    resizable,
+                } => {
+                    // Rows are the table's row children, every slotted child of a row is a cell
+                    let elements_children_borrow = self.elements_children.borrow();
+                    let rows: Vec<ElementId> = elements_children_borrow
+                        .get(&element_id)
+                        .map(|children| {
+                            children
+                                .iter()
+                                .copied()
+                                .filter(|child_id| {
+                                    let child_ref =
+                                        elements_borrow.get(child_id).expect("Failed to get child");
+                                    let is_row = matches!(&*child_ref.borrow(), Element::Row);
+                                    is_row
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    let row_height = ui.spacing().interact_size.y;
+
+                    ui.push_id(("table", element_id), |ui| {
+                        let mut table = egui_extras::TableBuilder::new(ui)
+                            .striped(*striped)
+                            .resizable(*resizable);
+                        for column in columns.iter() {
+                            table = table.column(match column.width {
+                                Some(width) => egui_extras::Column::initial(width),
+                                None => egui_extras::Column::auto(),
+                            });
+                        }
+                        table
+                            .header(row_height, |mut header| {
+                                for column in columns.iter() {
+                                    header.col(|ui| {
+                                        ui.strong(column.title.clone());
+                                    });
+                                }
+                            })
+                            .body(|mut body| {
+                                for row_id in rows {
+                                    body.row(row_height, |mut row| {
+                                        // fragment anchors and `v-if` comments don't take a column
+                                        for cell_id in self.slotted_children(row_id) {
+                                            row.col(|ui| {
+                                                responses.extend(self.render_element(ui, cell_id));
+                                            });
+                                        }
+                                    });
+                                }
+                            });
+                    });
+                }
+                Element::Area {
+                    position,
+                    draggable,
+                } => {
+                    // Areas float above the layout like windows, draggable ones only use the
+                    // position as a starting point and keep wherever the user moved them
+                    let mut area =
+                        egui::Area::new(egui::Id::new(("area", element_id))).movable(*draggable);
+                    area = if *draggable {
+                        area.default_pos(*position)
+                    } else {
+                        area.fixed_pos(*position)
+                    };
+                    let area_response =
+                        area.show(ui.ctx(), |ui| self.render_children(ui, element_id));
+                    responses.push(area_response.response);
+                    responses.extend(area_response.inner);
+                }
+                Element::Window {
+                    title,
+                    open,
+                    resizable,
+                    position,
+                } => {
+                    // Windows are not part of the parent's layout flow, they live in their own
+                    // layer on the context and are only anchored to the tree for their lifetime
+                    let was_open = *open;
+                    let mut window = egui::Window::new(title.clone())
+                        .id(egui::Id::new(("window", element_id)))
+                        .open(open)
+                        .resizable(*resizable);
+                    if let Some(position) = position {
+                        window = window.default_pos(*position);
+                    }
+                    let window_response =
+                        window.show(ui.ctx(), |ui| self.render_children(ui, element_id));
+                    if let Some(window_response) = window_response {
+                        responses.push(window_response.response);
+                        if let Some(inner_responses) = window_response.inner {
+                            responses.extend(inner_responses);
+                        }
+                    }
+
+                    if *open != was_open {
+                        let element_events_borrow = self.element_events.borrow();
+                        let update_open = element_events_borrow
+                            .get(&element_id)
+                            .and_then(|events| events.update_open.as_ref());
+                        if let Some(update_open) = update_open {
+                            call_handler(
+                                &self.isolate,
+                                update_open,
+                                "update:open",
+                                vec![Value::Boolean(*open)],
+                            );
+                        }
+                    }
+                }
             }
-            Element::Separator => {
-                ui.separator();
+        });
+        let rect = rendered.response.rect;
+        if dragging {
+            egui::DragAndDrop::set_payload(ui.ctx(), DragSource(element_id));
+            if let Some(pointer) = ui.ctx().pointer_interact_pos() {
+                ui.ctx()
+                    .translate_layer(drag_layer, pointer - rect.center());
+            }
+        } else if draggable {
+            ui.interact(rect, drag_id, egui::Sense::drag())
+                .on_hover_cursor(egui::CursorIcon::Grab);
+        }
+        if let Some((_, translation)) = animation.filter(|_| sliding && !dragging) {
+            ui.ctx().translate_layer(animation_layer, translation);
+        }
+
+        // Apply the generic props to the element's widget
+        let element_props_borrow = self.element_props.borrow();
+        if let Some(props) = element_props_borrow.get(&element_id) {
+            if let (Some(tooltip), Some(response)) = (&props.tooltip, responses.first()) {
+                match props.tooltip_delay.or(self.tooltip_delay.get()) {
+                    Some(delay) => show_delayed_tooltip(response, element_id, tooltip, delay),
+                    None => {
+                        response.clone().on_hover_text(tooltip.clone());
+                    }
+                }
+            }
+            if let (Some(cursor), Some(response)) = (props.cursor, responses.first()) {
+                response.clone().on_hover_cursor(cursor);
             }
-            Element::TextEdit(label) => {
-                let response = ui.text_edit_singleline(label);
-                responses.push(response);
+        }
+        if let Some(response) = responses.first() {
+            if self.focus_requests.borrow_mut().remove(&element_id) {
+                response.request_focus();
+            }
+        }
+
+        // Attach context menus declared as children to the element's widget
+        if let Some(response) = responses.first().cloned() {
+            let elements_children_borrow = self.elements_children.borrow();
+            let context_menus: Vec<ElementId> = elements_children_borrow
+                .get(&element_id)
+                .map(|children| {
+                    children
+                        .iter()
+                        .copied()
+                        .filter(|child_id| {
+                            let child_ref =
+                                elements_borrow.get(child_id).expect("Failed to get child");
+                            let is_context_menu =
+                                matches!(&*child_ref.borrow(), Element::ContextMenu);
+                            is_context_menu
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            for context_menu_id in context_menus {
+                response.clone().context_menu(|ui| {
+                    responses.extend(self.render_children(ui, context_menu_id));
+                });
             }
         }
 
         // Hook up events
         let element_events_borrow = self.element_events.borrow();
-        let events = element_events_borrow.get(&element_id);
+        // Enter and leave only fire when the hover state changes, not on every hovered frame.
+        // Disabling an element counts as leaving it, elements that stop rendering leave in
+        // `run_visibility_events`
+        let hovered = !disabled && responses.iter().any(|response| response.hovered());
+        let was_hovered = if hovered {
+            !self.hovered_elements.borrow_mut().insert(element_id)
+        } else {
+            self.hovered_elements.borrow_mut().remove(&element_id)
+        };
+        let hover_events = element_events_borrow.get(&element_id);
+        let hover_event = match (was_hovered, hovered) {
+            (false, true) => Some((
+                "mouseenter",
+                hover_events.and_then(|e| e.mouse_enter.as_ref()),
+            )),
+            (true, false) => Some((
+                "mouseleave",
+                hover_events.and_then(|e| e.mouse_leave.as_ref()),
+            )),
+            _ => None,
+        };
+        if let Some((event_type, Some(handler))) = hover_event {
+            let event = EventBuilder::new(&self.isolate, event_type, element_id)
+                .input(ui.ctx())
+                .build();
+            call_handler(&self.isolate, handler, event_type, vec![event]);
+        }
+        let events = element_events_borrow.get(&element_id).filter(|_| !disabled);
         if let Some(events) = events {
+            // other elements only get key events while focused
+            let focused = responses.iter().any(|response| response.has_focus());
+            if focused && !self.is_root_child(element_id) {
+                self.call_key_events(element_id, events);
+            }
+            let is_text_edit = matches!(
+                &*element,
+                Element::TextEdit { .. } | Element::TextArea { .. }
+            );
+            if focused && is_text_edit {
+                self.call_composition_events(element_id, events);
+            }
+            if let Some(wheel) = &events.wheel {
+                let delta = ui.input(|i| i.raw_scroll_delta);
+                if delta != egui::Vec2::ZERO && rendered.response.contains_pointer() {
+                    let event = EventBuilder::new(&self.isolate, "wheel", element_id)
+                        .input(ui.ctx())
+                        .field("deltaX", delta.x)
+                        .field("deltaY", delta.y)
+                        .build();
+                    call_handler(&self.isolate, wheel, "wheel", vec![event]);
+                }
+            }
+            // Pointer drags on the element itself, with the delta of this frame (dx, dy) and
+            // the one accumulated since the drag started (totalX, totalY)
+            let has_drag_handler =
+                events.drag_start.is_some() || events.drag.is_some() || events.drag_end.is_some();
+            let drag_response = has_drag_handler
+                .then(|| rendered.response.interact(egui::Sense::drag()))
+                .filter(|response| {
+                    response.drag_started() || response.dragged() || response.drag_stopped()
+                });
+            if let Some(response) = &drag_response {
+                let delta = response.drag_delta();
+                let total = {
+                    let mut drag_totals = self.drag_totals.borrow_mut();
+                    if response.drag_started() {
+                        drag_totals.insert(element_id, egui::Vec2::ZERO);
+                    }
+                    let total = drag_totals.entry(element_id).or_default();
+                    *total += delta;
+                    let total = *total;
+                    if response.drag_stopped() {
+                        drag_totals.remove(&element_id);
+                    }
+                    total
+                };
+                let drag_events = [
+                    ("dragstart", &events.drag_start, response.drag_started()),
+                    ("drag", &events.drag, response.dragged()),
+                    ("dragend", &events.drag_end, response.drag_stopped()),
+                ];
+                for (event_type, handler, fired) in drag_events {
+                    let Some(handler) = handler.as_ref().filter(|_| fired) else {
+                        continue;
+                    };
+                    let event = EventBuilder::new(&self.isolate, event_type, element_id)
+                        .input(ui.ctx())
+                        .field("dx", delta.x)
+                        .field("dy", delta.y)
+                        .field("totalX", total.x)
+                        .field("totalY", total.y)
+                        .build();
+                    call_handler(&self.isolate, handler, event_type, vec![event]);
+                }
+            }
+            // Elements with an onDrop handler accept dragged elements, nested targets are
+            // checked first and take the payload
+            if let Some(drop) = &events.drop {
+                let target = &rendered.response;
+                let hovered_source = target
+                    .dnd_hover_payload::<DragSource>()
+                    .filter(|source| source.0 != element_id);
+                if hovered_source.is_some() {
+                    ui.painter()
+                        .rect_stroke(rect, 2.0, ui.visuals().widgets.hovered.fg_stroke);
+                }
+                let released_source = target
+                    .dnd_release_payload::<DragSource>()
+                    .filter(|source| source.0 != element_id);
+                if let Some(source) = released_source {
+                    let event = EventBuilder::new(&self.isolate, "drop", element_id)
+                        .input(ui.ctx())
+                        .source(source.0)
+                        .build();
+                    call_handler(&self.isolate, drop, "drop", vec![event]);
+                }
+            }
             for response in &responses {
                 if let Some(click) = &events.click {
                     if response.clicked() {
-                        click
-                            .call::<(), ()>(().into())
-                            .expect("Failed to call click event");
+                        let event = EventBuilder::new(&self.isolate, "click", element_id)
+                            .input(&response.ctx)
+                            .build();
+                        call_handler(&self.isolate, click, "click", vec![event]);
                     }
                 }
-                if let Some(hover) = &events.hover {
-                    if response.hovered() {
-                        hover
-                            .call::<(), ()>(().into())
-                            .expect("Failed to call hover event");
+                if let Some(focus) = &events.focus {
+                    if response.gained_focus() {
+                        let event = EventBuilder::new(&self.isolate, "focus", element_id)
+                            .input(&response.ctx)
+                            .build();
+                        call_handler(&self.isolate, focus, "focus", vec![event]);
                     }
                 }
+                if let Some(blur) = &events.blur {
+                    if response.lost_focus() {
+                        let event = EventBuilder::new(&self.isolate, "blur", element_id)
+                            .input(&response.ctx)
+                            .build();
+                        call_handler(&self.isolate, blur, "blur", vec![event]);
+                    }
+                }
+                // The new value of value-bearing widgets, if the user changed it this frame
+                let changed_value = match &*element {
+                    _ if !response.changed() => None,
+                    Element::Checkbox(_, checked) => Some(Value::Boolean(*checked)),
+                    Element::Slider { value, .. } => Some(Value::Number(*value)),
+                    Element::Number { value, .. } => Some(Value::Number(*value)),
+                    Element::RadioGroup(selected) => Some(
+                        selected
+                            .clone()
+                            .to_value(&self.isolate)
+                            .expect("Failed to convert radio group value"),
+                    ),
+                    Element::Tabs(active) => Some(Value::Number(*active as f64)),
+                    Element::Segmented { selected, .. } => Some(
+                        selected
+                            .clone()
+                            .to_value(&self.isolate)
+                            .expect("Failed to convert segmented value"),
+                    ),
+                    Element::Split { ratio, .. } => Some(Value::Number(*ratio as f64)),
+                    _ => None,
+                };
+                let text = match &*element {
+                    Element::TextEdit { text, .. } | Element::TextArea { text, .. } => Some(text),
+                    _ => None,
+                };
                 if let Some(input) = &events.input {
-                    if let Element::TextEdit(label) = &*element {
-                        if response.lost_focus() {
-                            input
-                                .call::<Variadic<Value>, ()>(Variadic::from_vec(vec![label
-                                    .clone()
-                                    .to_value(&self.isolate)
-                                    .expect("Failed to convert text edit value")]))
-                                .expect("Failed to call input event");
-                        }
+                    // text edits report every keystroke
+                    let value = match text {
+                        Some(text) if response.changed() => Some(
+                            text.clone()
+                                .to_value(&self.isolate)
+                                .expect("Failed to convert text value"),
+                        ),
+                        Some(_) => None,
+                        None => changed_value.clone(),
+                    };
+                    if let Some(value) = value {
+                        // the value stays the first argument, the event object follows it
+                        let event = EventBuilder::new(&self.isolate, "input", element_id)
+                            .input(&response.ctx)
+                            .value(value.clone())
+                            .build();
+                        call_handler(&self.isolate, input, "input", vec![value, event]);
+                    }
+                }
+                if let Some(update_model_value) = &events.update_model_value {
+                    // v-model, the handler only takes the new value like a component's would
+                    let value = match text {
+                        Some(text) if response.changed() => Some(
+                            text.clone()
+                                .to_value(&self.isolate)
+                                .expect("Failed to convert text value"),
+                        ),
+                        Some(_) => None,
+                        None => changed_value.clone(),
+                    };
+                    if let Some(value) = value {
+                        call_handler(
+                            &self.isolate,
+                            update_model_value,
+                            "update:modelValue",
+                            vec![value],
+                        );
+                    }
+                }
+                if let Some(commit) = &events.commit {
+                    // the finished text, once the edit loses focus
+                    if let Some(text) = text.filter(|_| response.lost_focus()) {
+                        let value = text
+                            .clone()
+                            .to_value(&self.isolate)
+                            .expect("Failed to convert text value");
+                        let event = EventBuilder::new(&self.isolate, "commit", element_id)
+                            .input(&response.ctx)
+                            .value(value.clone())
+                            .build();
+                        call_handler(&self.isolate, commit, "commit", vec![value, event]);
+                    }
+                }
+                if let Some(submit) = &events.submit {
+                    // single-line edits lose focus on Enter
+                    let submitted = response.lost_focus()
+                        && response.ctx.input(|i| i.key_pressed(egui::Key::Enter));
+                    if let (Element::TextEdit { text, .. }, true) = (&*element, submitted) {
+                        let value = text
+                            .clone()
+                            .to_value(&self.isolate)
+                            .expect("Failed to convert text value");
+                        let event = EventBuilder::new(&self.isolate, "submit", element_id)
+                            .input(&response.ctx)
+                            .value(value.clone())
+                            .build();
+                        call_handler(&self.isolate, submit, "submit", vec![value, event]);
+                    }
+                }
+                if let Some(change) = &events.change {
+                    if let Some(value) = changed_value {
+                        let event = EventBuilder::new(&self.isolate, "change", element_id)
+                            .input(&response.ctx)
+                            .value(value.clone())
+                            .build();
+                        call_handler(&self.isolate, change, "change", vec![value, event]);
                     }
                 }
             }
@@ -965,21 +5841,306 @@ try {
 
         responses
     }
-    fn run_microtasks(&self) {
+    fn collect_frame_input(&self, ctx: &egui::Context) {
+        let mut frame_input = self.frame_input.borrow_mut();
+        ctx.input(|i| {
+            frame_input.hovered_files = i.raw.hovered_files.len();
+            frame_input.dropped_files = i
+                .raw
+                .dropped_files
+                .iter()
+                .map(|file| match &file.path {
+                    Some(path) => path.display().to_string(),
+                    None => file.name.clone(),
+                })
+                .collect();
+            frame_input.key_events = i
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Key {
+                        key,
+                        pressed,
+                        modifiers,
+                        ..
+                    } => Some(KeyEvent {
+                        key: *key,
+                        pressed: *pressed,
+                        modifiers: *modifiers,
+                    }),
+                    _ => None,
+                })
+                .collect();
+            frame_input.composition_events.clear();
+            for event in &i.events {
+                let egui::Event::Ime(ime_event) = event else {
+                    continue;
+                };
+                match ime_event {
+                    egui::ImeEvent::Preedit(text) => {
+                        if !frame_input.composing && !text.is_empty() {
+                            frame_input.composing = true;
+                            frame_input.composition_events.push(CompositionEvent::Start);
+                        }
+                        if frame_input.composing {
+                            frame_input
+                                .composition_events
+                                .push(CompositionEvent::Update(text.clone()));
+                        }
+                    }
+                    egui::ImeEvent::Commit(text) => {
+                        if !frame_input.composing {
+                            frame_input.composition_events.push(CompositionEvent::Start);
+                        }
+                        frame_input.composing = false;
+                        frame_input
+                            .composition_events
+                            .push(CompositionEvent::End(text.clone()));
+                    }
+                    egui::ImeEvent::Enabled | egui::ImeEvent::Disabled => {}
+                }
+            }
+        });
+    }
+
+    // Calls the composition handlers of a focused text edit with this frame's IME events
+    fn call_composition_events(&self, element_id: ElementId, events: &Events) {
+        let composition_events = self.frame_input.borrow().composition_events.clone();
+        for composition_event in composition_events {
+            let (event_type, handler, data) = match composition_event {
+                CompositionEvent::Start => {
+                    ("compositionstart", &events.composition_start, String::new())
+                }
+                CompositionEvent::Update(data) => {
+                    ("compositionupdate", &events.composition_update, data)
+                }
+                CompositionEvent::End(data) => ("compositionend", &events.composition_end, data),
+            };
+            if let Some(handler) = handler {
+                let event = EventBuilder::new(&self.isolate, event_type, element_id)
+                    .field("data", data)
+                    .build();
+                call_handler(&self.isolate, handler, event_type, vec![event]);
+            }
+        }
+    }
+
+    // Calls the keyboard handlers of an element with this frame's key events
+    fn call_key_events(&self, element_id: ElementId, events: &Events) {
+        let key_events = self.frame_input.borrow().key_events.clone();
+        for key_event in key_events {
+            let (event_type, handler) = if key_event.pressed {
+                ("keydown", &events.key_down)
+            } else {
+                ("keyup", &events.key_up)
+            };
+            if let Some(handler) = handler {
+                let event = EventBuilder::new(&self.isolate, event_type, element_id)
+                    .key(&key_event)
+                    .build();
+                call_handler(&self.isolate, handler, event_type, vec![event]);
+            }
+        }
+    }
+
+    fn is_root_child(&self, element_id: ElementId) -> bool {
+        self.elements_children
+            .borrow()
+            .get(&0)
+            .is_some_and(|children| children.contains(&element_id))
+    }
+
+    // The app's top level elements get all key events, whether they have focus or not
+    fn run_root_key_events(&self) {
+        let root_children = self
+            .elements_children
+            .borrow()
+            .get(&0)
+            .cloned()
+            .unwrap_or_default();
+        let element_events_borrow = self.element_events.borrow();
+        for child_id in root_children {
+            if let Some(events) = element_events_borrow.get(&child_id) {
+                self.call_key_events(child_id, events);
+            }
+        }
+    }
+
+    // Lets an element's onPaste handler rewrite the text pasted this frame before the widgets
+    // see it, returning a string replaces the text and returning `false` drops the paste
+    fn intercept_paste(&self, ctx: &egui::Context, element_id: ElementId) {
+        let element_events_borrow = self.element_events.borrow();
+        let Some(paste) = element_events_borrow
+            .get(&element_id)
+            .and_then(|events| events.paste.as_ref())
+        else {
+            return;
+        };
+        let pasted: Vec<String> = ctx.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                })
+                .collect()
+        });
+        if pasted.is_empty() {
+            return;
+        }
+
+        let replacements: Vec<Option<String>> = pasted
+            .into_iter()
+            .map(|text| {
+                let value = text
+                    .clone()
+                    .to_value(&self.isolate)
+                    .expect("Failed to convert pasted text");
+                let event = EventBuilder::new(&self.isolate, "paste", element_id)
+                    .input(ctx)
+                    .value(value.clone())
+                    .build();
+                // a throwing handler leaves the paste as it is
+                match call_handler(&self.isolate, paste, "paste", vec![value, event]) {
+                    Some(Value::Boolean(false)) => None,
+                    Some(Value::String(replacement)) => Some(replacement.to_string()),
+                    _ => Some(text),
+                }
+            })
+            .collect();
+        ctx.input_mut(|i| {
+            let mut replacements = replacements.into_iter();
+            i.events.retain_mut(|event| match event {
+                egui::Event::Paste(text) => match replacements.next().flatten() {
+                    Some(replacement) => {
+                        *text = replacement;
+                        true
+                    }
+                    None => false,
+                },
+                _ => true,
+            });
+        });
+    }
+
+    // Top level elements see every paste, before the focused text edit does
+    fn run_root_paste_events(&self, ctx: &egui::Context) {
+        let root_children = self
+            .elements_children
+            .borrow()
+            .get(&0)
+            .cloned()
+            .unwrap_or_default();
+        for child_id in root_children {
+            self.intercept_paste(ctx, child_id);
+        }
+    }
+
+    // Fires onShown for elements rendered for the first time since they were last hidden, and
+    // onHidden for elements that stopped being rendered (v-show, collapsed headers, closed tabs,
+    // removed elements), plus onMouseLeave for those of them that were hovered
+    fn run_visibility_events(&self, ctx: &egui::Context) {
+        let (shown, hidden) = {
+            let mut rendered_elements = self.rendered_elements.borrow_mut();
+            let current = std::mem::take(&mut rendered_elements.current);
+            let previous = std::mem::replace(&mut rendered_elements.previous, current);
+            let current = &rendered_elements.previous;
+            let shown: Vec<ElementId> = current.difference(&previous).copied().collect();
+            let hidden: Vec<ElementId> = previous.difference(current).copied().collect();
+            (shown, hidden)
+        };
+
+        let element_events_borrow = self.element_events.borrow();
+        let call = |element_id: ElementId, event_type: &str, handler: Option<&Function>| {
+            if let Some(handler) = handler {
+                let event = EventBuilder::new(&self.isolate, event_type, element_id)
+                    .input(ctx)
+                    .build();
+                call_handler(&self.isolate, handler, event_type, vec![event]);
+            }
+        };
+        for element_id in shown {
+            let events = element_events_borrow.get(&element_id);
+            call(element_id, "shown", events.and_then(|e| e.shown.as_ref()));
+        }
+        for element_id in hidden {
+            let events = element_events_borrow.get(&element_id);
+            call(element_id, "hidden", events.and_then(|e| e.hidden.as_ref()));
+            if self.hovered_elements.borrow_mut().remove(&element_id) {
+                call(
+                    element_id,
+                    "mouseleave",
+                    events.and_then(|e| e.mouse_leave.as_ref()),
+                );
+            }
+        }
+    }
+
+    // Finishes the transitions that ran their course, Vue removes the elements of finished leave
+    // transitions, so this has to run outside of rendering
+    fn run_animations(&self, ctx: &egui::Context) {
+        let finished: Vec<Function> = {
+            let mut animations = self.animations.borrow_mut();
+            let finished_ids: Vec<ElementId> = animations
+                .iter()
+                .filter(|(_, animation)| animation.finished())
+                .map(|(id, _)| *id)
+                .collect();
+            if animations.len() > finished_ids.len() {
+                ctx.request_repaint();
+            }
+            finished_ids
+                .into_iter()
+                .filter_map(|id| animations.remove(&id))
+                .map(|animation| animation.done().clone())
+                .collect()
+        };
+        for done in finished {
+            call_handler(&self.isolate, &done, "transition", Vec::new());
+        }
+    }
+
+    // Settles the promises that handlers and scripts left pending, when that changed the tree
+    // (an async component loaded, a <Suspense> resolved) another frame is needed to show it.
+    // Rejections still unhandled afterwards are reported, they'd go unnoticed otherwise
+    fn run_microtasks(&self, ctx: &egui::Context) {
         self.isolate.run_microtasks();
+        for reason in self.isolate.take_unhandled_rejections() {
+            report_unhandled_rejection(&self.isolate, reason);
+        }
+        if self.tree_changed.replace(false) {
+            ctx.request_repaint();
+        }
     }
 }
 
 impl eframe::App for GuiApp {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.theme.apply(ctx);
+        self.sync_zoom(ctx);
+        self.window.update(&self.isolate, ctx);
+        self.timers.run(&self.isolate, ctx);
+        self.animation_frames.run(&self.isolate, ctx);
+        self.fetch.run(&self.isolate);
+        self.collect_frame_input(ctx);
+        self.global_hotkeys.run(&self.isolate, ctx);
+        self.shortcuts.run(&self.isolate, ctx);
+        self.run_shortcuts(ctx);
+        self.run_root_key_events();
+        self.run_root_paste_events(ctx);
+        self.run_microtasks(ctx);
+        self.render_menu_bars(ctx);
+        self.render_panels(ctx);
         egui::CentralPanel::default().show(ctx, |ui| {
             self.render_element(ui, 0);
-            self.run_microtasks();
 
             // Text editor test
             let mut code = String::new();
             let label = ui.label("Enter code:");
             ui.text_edit_singleline(&mut code).labelled_by(label.id);
         });
+        self.run_animations(ctx);
+        self.run_visibility_events(ctx);
+        self.run_microtasks(ctx);
     }
 }
@@ -0,0 +1,306 @@
+use mini_v8::{MiniV8, ToValue, Value};
+
+// Parses the static markup chunks Vue's compiler hoists out of templates, only what it
+// generates for vgui's tags: elements with quoted or bare attributes, text and comments
+#[derive(Debug, PartialEq)]
+pub enum Node {
+    Element {
+        tag: String,
+        // valueless attributes like `disabled` have no value
+        attributes: Vec<(String, Option<String>)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+    Comment(String),
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let character = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => name.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+                };
+                code.and_then(char::from_u32)
+            }
+        });
+        match (character, entity) {
+            (Some(character), Some((_, end))) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            // not an entity after all, keep the `&`
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn is_name_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '<' | '>' | '/' | '=' | '"' | '\'')
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{} at {} in static content", message, self.position)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        if self.rest().starts_with(expected) {
+            self.position += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        let rest = self.rest();
+        let length = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+        if length == 0 {
+            return Err(self.error("Expected a name"));
+        }
+        self.position += length;
+        Ok(rest[..length].to_string())
+    }
+
+    fn until(&mut self, end: &str) -> Result<&'a str, String> {
+        let rest = self.rest();
+        let length = rest
+            .find(end)
+            .ok_or_else(|| self.error(&format!("Expected {}", end)))?;
+        self.position += length + end.len();
+        Ok(&rest[..length])
+    }
+
+    fn attribute_value(&mut self) -> Result<String, String> {
+        for quote in ["\"", "'"] {
+            if self.eat(quote) {
+                return Ok(decode_entities(self.until(quote)?));
+            }
+        }
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| c.is_whitespace() || c == '>')
+            .unwrap_or(rest.len());
+        self.position += length;
+        Ok(decode_entities(&rest[..length]))
+    }
+
+    // Nodes up to the closing tag of `parent`, or the end of the source at the top level
+    fn nodes(&mut self, parent: Option<&str>) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+        loop {
+            if self.rest().is_empty() {
+                return match parent {
+                    Some(tag) => Err(self.error(&format!("Unclosed <{}>", tag))),
+                    None => Ok(nodes),
+                };
+            }
+            if self.eat("<!--") {
+                nodes.push(Node::Comment(self.until("-->")?.to_string()));
+            } else if self.eat("</") {
+                let tag = self.name()?;
+                self.skip_whitespace();
+                if !self.eat(">") {
+                    return Err(self.error("Expected >"));
+                }
+                if parent != Some(tag.as_str()) {
+                    return Err(self.error(&format!("Unexpected </{}>", tag)));
+                }
+                return Ok(nodes);
+            } else if self.eat("<") {
+                nodes.push(self.element()?);
+            } else {
+                let rest = self.rest();
+                let length = rest.find('<').unwrap_or(rest.len());
+                self.position += length;
+                nodes.push(Node::Text(decode_entities(&rest[..length])));
+            }
+        }
+    }
+
+    // An element after its `<`
+    fn element(&mut self) -> Result<Node, String> {
+        let tag = self.name()?;
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok(Node::Element {
+                    tag,
+                    attributes,
+                    children: Vec::new(),
+                });
+            }
+            if self.eat(">") {
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            let value = if self.eat("=") {
+                self.skip_whitespace();
+                Some(self.attribute_value()?)
+            } else {
+                None
+            };
+            attributes.push((name, value));
+        }
+        let children = self.nodes(Some(&tag))?;
+        Ok(Node::Element {
+            tag,
+            attributes,
+            children,
+        })
+    }
+}
+
+pub fn parse(source: &str) -> Result<Vec<Node>, String> {
+    Parser {
+        source,
+        position: 0,
+    }
+    .nodes(None)
+}
+
+// `{ type: 'element', tag, attributes: [[name, value]], children }`, `{ type: 'text', text }`
+// or `{ type: 'comment', text }`, a valueless attribute is `true`
+impl ToValue for Node {
+    fn to_value(self, mv8: &MiniV8) -> mini_v8::Result<Value> {
+        let object = mv8.create_object();
+        match self {
+            Node::Element {
+                tag,
+                attributes,
+                children,
+            } => {
+                object.set("type", "element")?;
+                object.set("tag", tag)?;
+                let attributes = attributes
+                    .into_iter()
+                    .map(|(name, value)| {
+                        let value = match value {
+                            Some(value) => value.to_value(mv8)?,
+                            None => Value::Boolean(true),
+                        };
+                        vec![name.to_value(mv8)?, value].to_value(mv8)
+                    })
+                    .collect::<mini_v8::Result<Vec<Value>>>()?;
+                object.set("attributes", attributes)?;
+                object.set("children", children)?;
+            }
+            Node::Text(text) => {
+                object.set("type", "text")?;
+                object.set("text", text)?;
+            }
+            Node::Comment(text) => {
+                object.set("type", "comment")?;
+                object.set("text", text)?;
+            }
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(tag: &str, attributes: &[(&str, Option<&str>)], children: Vec<Node>) -> Node {
+        Node::Element {
+            tag: tag.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.map(str::to_string)))
+                .collect(),
+            children,
+        }
+    }
+
+    #[test]
+    fn parses_nested_elements() {
+        let nodes = parse(
+            r#"<vertical class="panel"><label tooltip='Hi &amp; bye'>A &lt; B</label><separator/></vertical>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            nodes,
+            vec![element(
+                "vertical",
+                &[("class", Some("panel"))],
+                vec![
+                    element(
+                        "label",
+                        &[("tooltip", Some("Hi & bye"))],
+                        vec![Node::Text("A < B".to_string())],
+                    ),
+                    element("separator", &[], vec![]),
+                ],
+            )]
+        );
+    }
+
+    #[test]
+    fn parses_siblings_text_and_comments() {
+        let nodes =
+            parse("<button disabled>Go</button> <!-- note --><spacer size=8></spacer>").unwrap();
+        assert_eq!(
+            nodes,
+            vec![
+                element(
+                    "button",
+                    &[("disabled", None)],
+                    vec![Node::Text("Go".to_string())],
+                ),
+                Node::Text(" ".to_string()),
+                Node::Comment(" note ".to_string()),
+                element("spacer", &[("size", Some("8"))], vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_numeric_entities() {
+        assert_eq!(
+            decode_entities("&#65;&#x42;&unknown; & c"),
+            "AB&unknown; & c"
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_tags() {
+        assert!(parse("<vertical><label></vertical>").is_err());
+        assert!(parse("<vertical>").is_err());
+        assert!(parse("</label>").is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
+
+use mini_v8::{Error as MiniV8Error, ModuleLoader};
+
+use crate::env::cli_or_env;
+
+fn external_error(message: String) -> MiniV8Error {
+    MiniV8Error::ExternalError(message.into())
+}
+
+// `--app <path>` on the command line or the `VGUI_APP` environment variable, an ES module that
+// creates and mounts the app in place of the built-in demo
+pub fn entry_from_env() -> Option<PathBuf> {
+    cli_or_env("--app", "VGUI_APP").map(PathBuf::from)
+}
+
+// Resolves `.` and `..` without touching the filesystem, modules are cached by these paths
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+// A module re-exporting the properties of a global, for the libraries loaded as global builds
+pub fn global_module(global: &str, names: &[String]) -> String {
+    let is_identifier = |name: &&String| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+            && name.as_str() != "default"
+    };
+    let names: Vec<&str> = names
+        .iter()
+        .filter(is_identifier)
+        .map(String::as_str)
+        .collect();
+    format!(
+        "const module = globalThis.{};\nexport default module;\nexport const {{ {} }} = module;\n",
+        global,
+        names.join(", ")
+    )
+}
+
+// Loads modules from the filesystem. Relative specifiers resolve against the importing module,
+// bare ones through the built-in modules and then the `imports` of an `importmap.json` next to
+// the entry module, where a key ending in `/` maps every specifier starting with it
+pub struct FileLoader {
+    root: PathBuf,
+    imports: Vec<(String, String)>,
+    builtins: HashMap<String, String>,
+}
+
+impl FileLoader {
+    pub fn new(root: &Path, builtins: HashMap<String, String>) -> Self {
+        let import_map = root.join("importmap.json");
+        let imports = match std::fs::read_to_string(&import_map) {
+            Ok(json) => match serde_json::from_str::<serde_json::Value>(&json) {
+                Ok(map) => map["imports"]
+                    .as_object()
+                    .map(|imports| {
+                        imports
+                            .iter()
+                            .filter_map(|(specifier, target)| {
+                                Some((specifier.clone(), target.as_str()?.to_string()))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                Err(e) => {
+                    println!("Failed to parse {}: {}", import_map.display(), e);
+                    Vec::new()
+                }
+            },
+            Err(_) => Vec::new(),
+        };
+        Self {
+            root: root.to_path_buf(),
+            imports,
+            builtins,
+        }
+    }
+
+    fn map_bare(&self, specifier: &str) -> Option<String> {
+        if let Some((_, target)) = self.imports.iter().find(|(key, _)| key == specifier) {
+            return Some(target.clone());
+        }
+        // the longest matching prefix wins
+        self.imports
+            .iter()
+            .filter(|(key, _)| key.ends_with('/') && specifier.starts_with(key.as_str()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(key, target)| format!("{}{}", target, &specifier[key.len()..]))
+    }
+}
+
+impl ModuleLoader for FileLoader {
+    fn resolve(&self, specifier: &str, referrer: &str) -> mini_v8::Result<String> {
+        if self.builtins.contains_key(specifier) {
+            return Ok(specifier.to_string());
+        }
+        let is_relative =
+            |specifier: &str| specifier.starts_with("./") || specifier.starts_with("../");
+        let path = if is_relative(specifier) {
+            let base = Path::new(referrer)
+                .parent()
+                .filter(|_| !referrer.is_empty())
+                .unwrap_or(&self.root);
+            base.join(specifier)
+        } else if Path::new(specifier).is_absolute() {
+            PathBuf::from(specifier)
+        } else {
+            let target = self.map_bare(specifier).ok_or_else(|| {
+                external_error(format!(
+                    "Cannot resolve {} imported from {}, bare specifiers need an entry in importmap.json",
+                    specifier,
+                    if referrer.is_empty() { "the app" } else { referrer }
+                ))
+            })?;
+            if self.builtins.contains_key(&target) {
+                return Ok(target);
+            }
+            self.root.join(target)
+        };
+
+        let path = normalize(&path);
+        // `./utils` imports `./utils.js`
+        let path = if !path.is_file() && path.extension().is_none() {
+            path.with_extension("js")
+        } else {
+            path
+        };
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    fn load(&self, name: &str) -> mini_v8::Result<String> {
+        if let Some(source) = self.builtins.get(name) {
+            return Ok(source.clone());
+        }
+        std::fs::read_to_string(name)
+            .map_err(|e| external_error(format!("Failed to read module {}: {}", name, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loader(imports: &[(&str, &str)]) -> FileLoader {
+        FileLoader {
+            root: PathBuf::from("/app"),
+            imports: imports
+                .iter()
+                .map(|(key, target)| (key.to_string(), target.to_string()))
+                .collect(),
+            builtins: HashMap::from([("vue".to_string(), String::new())]),
+        }
+    }
+
+    #[test]
+    fn resolves_relative_specifiers() {
+        let loader = loader(&[]);
+        assert_eq!(loader.resolve("./main.js", "").unwrap(), "/app/main.js");
+        assert_eq!(
+            loader
+                .resolve("../shared/util", "/app/views/home.js")
+                .unwrap(),
+            "/app/shared/util.js"
+        );
+        assert_eq!(
+            loader.resolve("./a/./b.mjs", "/app/main.js").unwrap(),
+            "/app/a/b.mjs"
+        );
+    }
+
+    #[test]
+    fn resolves_bare_specifiers() {
+        let loader = loader(&[
+            ("lodash", "./vendor/lodash.js"),
+            ("components/", "./src/components/"),
+            ("components/forms/", "./src/forms/"),
+            ("@vue", "vue"),
+        ]);
+        assert_eq!(loader.resolve("vue", "/app/main.js").unwrap(), "vue");
+        assert_eq!(loader.resolve("@vue", "/app/main.js").unwrap(), "vue");
+        assert_eq!(
+            loader.resolve("lodash", "/app/main.js").unwrap(),
+            "/app/vendor/lodash.js"
+        );
+        assert_eq!(
+            loader
+                .resolve("components/button.js", "/app/main.js")
+                .unwrap(),
+            "/app/src/components/button.js"
+        );
+        assert_eq!(
+            loader
+                .resolve("components/forms/input.js", "/app/main.js")
+                .unwrap(),
+            "/app/src/forms/input.js"
+        );
+        assert!(loader.resolve("left-pad", "/app/main.js").is_err());
+    }
+
+    #[test]
+    fn global_modules_export_identifiers() {
+        let names = ["ref", "default", "3d", "computed"].map(String::from);
+        assert_eq!(
+            global_module("Vue", &names),
+            "const module = globalThis.Vue;\nexport default module;\nexport const { ref, computed } = module;\n"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    rc::Rc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use mini_v8::{Function, MiniV8, Object, Value};
+
+// The oldest entries are dropped past this, so a long running app can keep marking
+const MAX_ENTRIES: usize = 10_000;
+
+// `performance.now()`, `mark()`, `measure()` and the entry getters, the arguments are sorted out
+// here and the entries are recorded by the profiler
+const PRELUDE: &str = r#"
+(profiler, timeOrigin) => {
+    const markTime = (name) => {
+        if (typeof name === 'number') {
+            return name;
+        }
+        const mark = profiler.entries().filter((entry) =>
+            entry.entryType === 'mark' && entry.name === name).pop();
+        if (!mark) {
+            throw new SyntaxError(`The mark '${name}' does not exist`);
+        }
+        return mark.startTime;
+    };
+
+    globalThis.performance = {
+        timeOrigin,
+        now() {
+            return profiler.now();
+        },
+        // mark(name, { startTime, detail })
+        mark(name, options = {}) {
+            const startTime = options.startTime === undefined ? profiler.now() : options.startTime;
+            return profiler.record('mark', String(name), startTime, 0, options.detail);
+        },
+        // measure(name), measure(name, startMark, endMark) or
+        // measure(name, { start, end, duration, detail }), marks are names or times
+        measure(name, startOrOptions, endMark) {
+            let start = 0;
+            let end;
+            let detail;
+            if (startOrOptions !== null && typeof startOrOptions === 'object') {
+                const options = startOrOptions;
+                detail = options.detail;
+                if (options.start !== undefined) {
+                    start = markTime(options.start);
+                }
+                if (options.end !== undefined) {
+                    end = markTime(options.end);
+                }
+                if (options.duration !== undefined) {
+                    if (end === undefined) {
+                        end = start + options.duration;
+                    } else if (options.start === undefined) {
+                        start = end - options.duration;
+                    }
+                }
+            } else if (startOrOptions !== undefined) {
+                start = markTime(startOrOptions);
+            }
+            if (end === undefined) {
+                end = endMark === undefined ? profiler.now() : markTime(endMark);
+            }
+            return profiler.record('measure', String(name), start, end - start, detail);
+        },
+        getEntries() {
+            return profiler.entries();
+        },
+        getEntriesByName(name, type) {
+            return profiler.entries().filter((entry) =>
+                entry.name === name && (type === undefined || entry.entryType === type));
+        },
+        getEntriesByType(type) {
+            return profiler.entries().filter((entry) => entry.entryType === type);
+        },
+        clearMarks(name) {
+            profiler.clear('mark', name);
+        },
+        clearMeasures(name) {
+            profiler.clear('measure', name);
+        },
+    };
+}
+"#;
+
+struct Entry {
+    name: String,
+    entry_type: String,
+    start_time: f64,
+    duration: f64,
+    detail: Value,
+}
+
+impl Entry {
+    fn to_object(&self, isolate: &MiniV8) -> mini_v8::Result<Object> {
+        let object = isolate.create_object();
+        object.set("name", self.name.clone())?;
+        object.set("entryType", self.entry_type.clone())?;
+        object.set("startTime", self.start_time)?;
+        object.set("duration", self.duration)?;
+        object.set("detail", self.detail.clone())?;
+        Ok(object)
+    }
+}
+
+// Keeps the marks and measures made by scripts (measures are also traced on the `performance`
+// target) and the time origin `performance.now()` and animation frames count from
+pub struct Profiler {
+    origin: Instant,
+}
+
+impl Profiler {
+    pub fn new(isolate: &MiniV8) -> Self {
+        let origin = Instant::now();
+        let time_origin = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0);
+        let entries: Rc<RefCell<VecDeque<Entry>>> = Rc::new(RefCell::new(VecDeque::new()));
+        let profiler = isolate.create_object();
+
+        // now(), milliseconds since the origin
+        let now = isolate.create_function(move |_| Ok(origin.elapsed().as_secs_f64() * 1000.0));
+        profiler.set("now", now).expect("Failed to set now");
+
+        // record(entryType, name, startTime, duration, detail) returns the entry
+        let entries_clone = entries.clone();
+        let record = isolate.create_function(move |invocation| {
+            let (entry_type, name, start_time, duration, detail): (
+                String,
+                String,
+                f64,
+                f64,
+                Value,
+            ) = invocation.args.into(&invocation.mv8)?;
+            if entry_type == "measure" {
+                tracing::debug!(
+                    target: "performance",
+                    name = %name,
+                    duration_ms = duration,
+                    "measure"
+                );
+            }
+            let entry = Entry {
+                name,
+                entry_type,
+                start_time,
+                duration,
+                detail: if detail.is_undefined() {
+                    Value::Null
+                } else {
+                    detail
+                },
+            };
+            let object = entry.to_object(&invocation.mv8)?;
+            let mut entries = entries_clone.borrow_mut();
+            if entries.len() == MAX_ENTRIES {
+                entries.pop_front();
+            }
+            entries.push_back(entry);
+            Ok(object)
+        });
+        profiler
+            .set("record", record)
+            .expect("Failed to set record");
+
+        // entries(), ordered by start time
+        let entries_clone = entries.clone();
+        let list = isolate.create_function(move |invocation| {
+            let entries = entries_clone.borrow();
+            let mut sorted: Vec<&Entry> = entries.iter().collect();
+            sorted.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+            sorted
+                .into_iter()
+                .map(|entry| entry.to_object(&invocation.mv8))
+                .collect::<mini_v8::Result<Vec<Object>>>()
+        });
+        profiler
+            .set("entries", list)
+            .expect("Failed to set entries");
+
+        // clear(entryType, name), all entries of the type without a name
+        let clear = isolate.create_function(move |invocation| {
+            let (entry_type, name): (String, Option<String>) =
+                invocation.args.into(&invocation.mv8)?;
+            entries.borrow_mut().retain(|entry| {
+                entry.entry_type != entry_type
+                    || name.as_ref().is_some_and(|name| *name != entry.name)
+            });
+            Ok(())
+        });
+        profiler.set("clear", clear).expect("Failed to set clear");
+
+        isolate
+            .eval::<_, Function>(PRELUDE)
+            .and_then(|prelude| prelude.call::<_, ()>((profiler, time_origin)))
+            .expect("Failed to set performance");
+
+        Self { origin }
+    }
+
+    pub fn origin(&self) -> Instant {
+        self.origin
+    }
+}
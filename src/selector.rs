@@ -0,0 +1,154 @@
+// A minimal CSS selector engine for `querySelector`, compound selectors made of a tag (or `*`),
+// `#id` and `[attribute]` / `[attribute=value]` parts, several of them separated by commas
+#[derive(Debug, Default, PartialEq)]
+pub struct Selector {
+    tag: Option<String>,
+    id: Option<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || c == ':' || c == '@'
+}
+
+fn parse_name(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut name = String::new();
+    while let Some(c) = chars.next_if(|c| is_name_char(*c)) {
+        name.push(c);
+    }
+    name
+}
+
+fn parse_compound(source: &str) -> Result<Selector, String> {
+    let mut selector = Selector::default();
+    let mut chars = source.chars().peekable();
+    if chars.next_if_eq(&'*').is_none() {
+        let tag = parse_name(&mut chars);
+        if !tag.is_empty() {
+            selector.tag = Some(tag);
+        }
+    }
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                let id = parse_name(&mut chars);
+                if id.is_empty() {
+                    return Err(format!("Expected an id in selector {}", source));
+                }
+                selector.id = Some(id);
+            }
+            '[' => {
+                let body: String = chars.by_ref().take_while(|c| *c != ']').collect();
+                let (name, value) = match body.split_once('=') {
+                    Some((name, value)) => {
+                        let value = value.trim();
+                        let unquoted = value
+                            .strip_prefix('"')
+                            .and_then(|value| value.strip_suffix('"'))
+                            .or_else(|| {
+                                value
+                                    .strip_prefix('\'')
+                                    .and_then(|value| value.strip_suffix('\''))
+                            })
+                            .unwrap_or(value);
+                        (name.trim(), Some(unquoted.to_string()))
+                    }
+                    None => (body.trim(), None),
+                };
+                if name.is_empty() || !name.chars().all(is_name_char) {
+                    return Err(format!("Invalid attribute in selector {}", source));
+                }
+                selector.attributes.push((name.to_string(), value));
+            }
+            _ => return Err(format!("Unsupported selector {}", source)),
+        }
+    }
+    Ok(selector)
+}
+
+// Parses `selectors`, any of the comma separated selectors may match
+pub fn parse(selectors: &str) -> Result<Vec<Selector>, String> {
+    selectors
+        .split(',')
+        .map(|source| {
+            let source = source.trim();
+            if source.is_empty() {
+                return Err(format!("Empty selector in {}", selectors));
+            }
+            parse_compound(source)
+        })
+        .collect()
+}
+
+impl Selector {
+    // `attribute` looks up an attribute's value as a string, the id is the `id` attribute
+    pub fn matches(&self, tag: &str, attribute: impl Fn(&str) -> Option<String>) -> bool {
+        self.tag.as_ref().map_or(true, |expected| expected == tag)
+            && self
+                .id
+                .as_ref()
+                .map_or(true, |expected| attribute("id").as_ref() == Some(expected))
+            && self
+                .attributes
+                .iter()
+                .all(|(name, expected)| match (attribute(name), expected) {
+                    (Some(value), Some(expected)) => &value == expected,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes<'a>(attributes: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            attributes
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn parses_compound_selector() {
+        let selectors = parse("button#save[kind='primary'][disabled]").unwrap();
+        assert_eq!(
+            selectors,
+            vec![Selector {
+                tag: Some("button".to_string()),
+                id: Some("save".to_string()),
+                attributes: vec![
+                    ("kind".to_string(), Some("primary".to_string())),
+                    ("disabled".to_string(), None),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_selectors() {
+        assert!(parse("vertical > label").is_err());
+        assert!(parse(".class").is_err());
+        assert!(parse("label,").is_err());
+        assert!(parse("#").is_err());
+    }
+
+    #[test]
+    fn matches_tag_id_and_attributes() {
+        let attrs = [("id", "app"), ("role", "main")];
+        let matches =
+            |source: &str| parse(source).unwrap()[0].matches("vertical", attributes(&attrs));
+        assert!(matches("vertical"));
+        assert!(matches("*"));
+        assert!(matches("#app"));
+        assert!(matches("[role]"));
+        assert!(matches("vertical#app[role=main]"));
+        assert!(!matches("label"));
+        assert!(!matches("#other"));
+        assert!(!matches("[role=aside]"));
+        assert!(!matches("[missing]"));
+    }
+}
@@ -0,0 +1,113 @@
+use std::{cell::RefCell, rc::Rc};
+
+use eframe::egui;
+use mini_v8::{Error as MiniV8Error, Function, MiniV8, Object};
+
+use crate::{
+    event::{call_handler, EventBuilder},
+    parse_keyboard_shortcut,
+};
+
+struct RegisteredShortcut {
+    keys: String,
+    shortcut: egui::KeyboardShortcut,
+    handler: Function,
+}
+
+// The `vgui.shortcuts` object, app-wide shortcuts registered from scripts
+pub struct Shortcuts {
+    object: Object,
+    registered: Rc<RefCell<Vec<RegisteredShortcut>>>,
+}
+
+impl Shortcuts {
+    pub fn new(isolate: &MiniV8) -> Self {
+        let object = isolate.create_object();
+        let registered: Rc<RefCell<Vec<RegisteredShortcut>>> = Rc::new(RefCell::new(Vec::new()));
+
+        // register('Ctrl+Shift+P', handler), a shortcut can only be registered once
+        let registered_clone = registered.clone();
+        let register = isolate.create_function(move |invocation| {
+            let (keys, handler): (String, Function) = invocation.args.into(&invocation.mv8)?;
+            let shortcut = parse_keyboard_shortcut(&keys)
+                .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?;
+            let mut registered = registered_clone.borrow_mut();
+            if let Some(existing) = registered.iter().find(|r| r.shortcut == shortcut) {
+                return Err(MiniV8Error::ExternalError(
+                    format!(
+                        "Shortcut {} conflicts with the registered {}",
+                        keys, existing.keys
+                    )
+                    .into(),
+                ));
+            }
+            registered.push(RegisteredShortcut {
+                keys,
+                shortcut,
+                handler,
+            });
+            Ok(())
+        });
+        object
+            .set("register", register)
+            .expect("Failed to set shortcuts.register");
+
+        // unregister('Ctrl+Shift+P') -> whether the shortcut was registered
+        let registered_clone = registered.clone();
+        let unregister = isolate.create_function(move |invocation| {
+            let (keys,): (String,) = invocation.args.into(&invocation.mv8)?;
+            let shortcut = parse_keyboard_shortcut(&keys)
+                .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))?;
+            let mut registered = registered_clone.borrow_mut();
+            let count = registered.len();
+            registered.retain(|r| r.shortcut != shortcut);
+            Ok(registered.len() != count)
+        });
+        object
+            .set("unregister", unregister)
+            .expect("Failed to set shortcuts.unregister");
+
+        Self { object, registered }
+    }
+
+    pub fn object(&self) -> Object {
+        self.object.clone()
+    }
+
+    // Consumes the pressed shortcuts, so widgets don't also react to them, and calls their handlers
+    pub fn run(&self, isolate: &MiniV8, ctx: &egui::Context) {
+        let mut triggered: Vec<(String, Function)> = Vec::new();
+        {
+            let mut registered = self.registered.borrow_mut();
+            // Shortcuts with more modifiers go first, `Ctrl+Shift+P` would also match `Ctrl+P`
+            registered.sort_by_key(|r| {
+                let modifiers = r.shortcut.modifiers;
+                std::cmp::Reverse(
+                    [
+                        modifiers.alt,
+                        modifiers.ctrl,
+                        modifiers.shift,
+                        modifiers.mac_cmd,
+                        modifiers.command,
+                    ]
+                    .iter()
+                    .filter(|modifier| **modifier)
+                    .count(),
+                )
+            });
+            for r in registered.iter() {
+                if ctx.input_mut(|i| i.consume_shortcut(&r.shortcut)) {
+                    triggered.push((r.keys.clone(), r.handler.clone()));
+                }
+            }
+        }
+
+        for (keys, handler) in triggered {
+            let event = EventBuilder::new(isolate, "shortcut", 0)
+                .input(ctx)
+                .field("keys", keys)
+                .build();
+            call_handler(isolate, &handler, "shortcut", vec![event]);
+        }
+    }
+}
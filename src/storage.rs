@@ -0,0 +1,267 @@
+use std::{cell::RefCell, collections::BTreeMap, path::PathBuf, rc::Rc};
+
+use eyre::{Context, Result};
+use mini_v8::{Function, MiniV8, Object};
+
+// Wraps the native backend in a Proxy, so items also read and write as properties like
+// `localStorage.token = '...'`
+const PRELUDE: &str = r#"
+(backend) => {
+    const storage = {
+        getItem(key) {
+            return backend.get(String(key));
+        },
+        setItem(key, value) {
+            backend.set(String(key), String(value));
+        },
+        removeItem(key) {
+            backend.remove(String(key));
+        },
+        clear() {
+            backend.clear();
+        },
+        key(index) {
+            const keys = backend.keys();
+            return index >= 0 && index < keys.length ? keys[index] : null;
+        },
+        get length() {
+            return backend.keys().length;
+        },
+    };
+    const isItem = (target, key) => typeof key === 'string' && !(key in target);
+    return new Proxy(storage, {
+        get(target, key, receiver) {
+            if (!isItem(target, key)) {
+                return Reflect.get(target, key, receiver);
+            }
+            const value = backend.get(key);
+            return value === null ? undefined : value;
+        },
+        set(target, key, value, receiver) {
+            if (!isItem(target, key)) {
+                return Reflect.set(target, key, value, receiver);
+            }
+            backend.set(key, String(value));
+            return true;
+        },
+        has(target, key) {
+            return key in target || (typeof key === 'string' && backend.get(key) !== null);
+        },
+        deleteProperty(target, key) {
+            if (isItem(target, key)) {
+                backend.remove(key);
+            }
+            return true;
+        },
+        ownKeys() {
+            return backend.keys();
+        },
+        getOwnPropertyDescriptor(target, key) {
+            const value = isItem(target, key) ? backend.get(key) : null;
+            return value === null
+                ? undefined
+                : { value, writable: true, enumerable: true, configurable: true };
+        },
+    });
+}
+"#;
+
+// Where apps keep their data: %APPDATA% on Windows, ~/Library/Application Support on macOS and
+// $XDG_DATA_HOME (~/.local/share) elsewhere
+pub fn data_dir(app_name: &str) -> Option<PathBuf> {
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| home().map(|home| home.join(".local").join("share")))
+    }?;
+    let app_dir: String = app_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Some(base.join(app_dir))
+}
+
+// The items of a storage area, written back to `path` after every change when it has one
+struct Items {
+    items: BTreeMap<String, String>,
+    path: Option<PathBuf>,
+}
+
+impl Items {
+    fn load(path: Option<PathBuf>) -> Result<Self> {
+        let items = match &path {
+            Some(path) if path.is_file() => {
+                let json = std::fs::read_to_string(path)
+                    .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+                serde_json::from_str(&json)
+                    .wrap_err_with(|| format!("Failed to parse {}", path.display()))?
+            }
+            _ => BTreeMap::new(),
+        };
+        Ok(Self { items, path })
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let json = serde_json::to_string_pretty(&self.items).wrap_err("Failed to serialize")?;
+        std::fs::write(path, json).wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn update(&mut self, change: impl FnOnce(&mut BTreeMap<String, String>)) {
+        change(&mut self.items);
+        if let Err(e) = self.save() {
+            println!("Failed to persist storage: {:?}", e);
+        }
+    }
+}
+
+// A `localStorage` or `sessionStorage` object, `localStorage` keeps its items in a JSON file and
+// `sessionStorage` only for as long as the app runs
+pub struct WebStorage {
+    object: Object,
+}
+
+impl WebStorage {
+    pub fn local(isolate: &MiniV8, path: Option<PathBuf>) -> Self {
+        let items = Items::load(path.clone()).unwrap_or_else(|e| {
+            println!("Starting with an empty localStorage: {:?}", e);
+            Items {
+                items: BTreeMap::new(),
+                path,
+            }
+        });
+        Self::new(isolate, items)
+    }
+
+    pub fn session(isolate: &MiniV8) -> Self {
+        Self::new(
+            isolate,
+            Items {
+                items: BTreeMap::new(),
+                path: None,
+            },
+        )
+    }
+
+    fn new(isolate: &MiniV8, items: Items) -> Self {
+        let items = Rc::new(RefCell::new(items));
+        let backend = isolate.create_object();
+
+        // get(key), `null` for missing items
+        let items_clone = items.clone();
+        let get = isolate.create_function(move |invocation| {
+            let (key,): (String,) = invocation.args.into(&invocation.mv8)?;
+            Ok(items_clone.borrow().items.get(&key).cloned())
+        });
+        backend.set("get", get).expect("Failed to set get");
+
+        // set(key, value)
+        let items_clone = items.clone();
+        let set = isolate.create_function(move |invocation| {
+            let (key, value): (String, String) = invocation.args.into(&invocation.mv8)?;
+            let mut items = items_clone.borrow_mut();
+            if items.items.get(&key) != Some(&value) {
+                items.update(|items| {
+                    items.insert(key, value);
+                });
+            }
+            Ok(())
+        });
+        backend.set("set", set).expect("Failed to set set");
+
+        // remove(key)
+        let items_clone = items.clone();
+        let remove = isolate.create_function(move |invocation| {
+            let (key,): (String,) = invocation.args.into(&invocation.mv8)?;
+            let mut items = items_clone.borrow_mut();
+            if items.items.contains_key(&key) {
+                items.update(|items| {
+                    items.remove(&key);
+                });
+            }
+            Ok(())
+        });
+        backend.set("remove", remove).expect("Failed to set remove");
+
+        // clear()
+        let items_clone = items.clone();
+        let clear = isolate.create_function(move |_| {
+            items_clone.borrow_mut().update(BTreeMap::clear);
+            Ok(())
+        });
+        backend.set("clear", clear).expect("Failed to set clear");
+
+        // keys(), in a stable (sorted) order for key(index)
+        let keys = isolate.create_function(move |_| {
+            Ok(items
+                .borrow()
+                .items
+                .keys()
+                .cloned()
+                .collect::<Vec<String>>())
+        });
+        backend.set("keys", keys).expect("Failed to set keys");
+
+        let object = isolate
+            .eval::<_, Function>(PRELUDE)
+            .and_then(|prelude| prelude.call::<_, Object>((backend,)))
+            .expect("Failed to create storage");
+        Self { object }
+    }
+
+    pub fn object(&self) -> Object {
+        self.object.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_round_trip_through_the_file() {
+        let dir = std::env::temp_dir().join(format!("vgui-storage-{}", std::process::id()));
+        let path = dir.join("nested").join("localStorage.json");
+
+        let mut items = Items::load(Some(path.clone())).unwrap();
+        assert!(items.items.is_empty());
+        items.update(|items| {
+            items.insert("theme".to_string(), "dark".to_string());
+            items.insert("token".to_string(), "abc".to_string());
+        });
+        items.update(|items| {
+            items.remove("token");
+        });
+
+        let loaded = Items::load(Some(path)).unwrap();
+        assert_eq!(
+            loaded.items.into_iter().collect::<Vec<_>>(),
+            vec![("theme".to_string(), "dark".to_string())]
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn app_names_become_directory_names() {
+        let dir = data_dir("vgui demo/2").unwrap();
+        assert_eq!(dir.file_name().unwrap(), "vgui_demo_2");
+    }
+}
@@ -0,0 +1,48 @@
+use mini_v8::{Function, MiniV8, Object, ToValue};
+
+// A Pinia store seen from the host, by id through the `vgui.stores` bridge, the store itself
+// only exists once the app first used it
+pub struct Store {
+    isolate: MiniV8,
+    id: String,
+}
+
+impl Store {
+    pub fn new(isolate: &MiniV8, id: &str) -> Self {
+        Self {
+            isolate: isolate.clone(),
+            id: id.to_string(),
+        }
+    }
+
+    fn bridge(&self) -> mini_v8::Result<Object> {
+        self.isolate
+            .global()
+            .get::<_, Object>("vgui")?
+            .get::<_, Object>("stores")
+    }
+
+    // The store's reactive state, `None` while the store isn't in use
+    pub fn state(&self) -> mini_v8::Result<Option<Object>> {
+        self.bridge()?.call_prop("state", (self.id.clone(),))
+    }
+
+    // Merges `partial` into the state like `store.$patch` does
+    pub fn patch(&self, partial: impl ToValue) -> mini_v8::Result<()> {
+        let partial = partial.to_value(&self.isolate)?;
+        self.bridge()?
+            .call_prop("patch", (self.id.clone(), partial))
+    }
+
+    // Calls `callback` with the state after every change, also for stores used later on
+    pub fn watch(&self, callback: impl Fn(&MiniV8, Object) + 'static) -> mini_v8::Result<()> {
+        let callback: Function = self.isolate.create_function(move |invocation| {
+            let (state,): (Object,) = invocation.args.into(&invocation.mv8)?;
+            callback(&invocation.mv8, state);
+            Ok(())
+        });
+        self.bridge()?
+            .call_prop::<_, _, Function>("watch", (self.id.clone(), callback))
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,130 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use eframe::egui::{self, Color32};
+use mini_v8::{Error as MiniV8Error, MiniV8, Object, Value};
+
+use crate::value_to_color32;
+
+// A style rule, e.g. `{ color: '#f55', background: [40, 0, 0], bold: true, size: 18 }`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Style {
+    color: Option<Color32>,
+    background: Option<Color32>,
+    bold: Option<bool>,
+    size: Option<f32>,
+}
+
+impl Style {
+    fn from_object(isolate: &MiniV8, object: Object) -> mini_v8::Result<Self> {
+        let color = |key: &str| -> mini_v8::Result<Option<Color32>> {
+            let value: Value = object.get(key)?;
+            if value.is_undefined() || value.is_null() {
+                return Ok(None);
+            }
+            value_to_color32(isolate, &value)
+                .map(Some)
+                .map_err(|e| MiniV8Error::ExternalError(format!("{:?}", e).into()))
+        };
+        Ok(Style {
+            color: color("color")?,
+            background: color("background")?,
+            bold: object.get("bold")?,
+            size: object
+                .get::<_, Option<f64>>("size")?
+                .map(|size| size as f32),
+        })
+    }
+
+    // Properties set in `other` win
+    fn merge(self, other: Style) -> Style {
+        Style {
+            color: other.color.or(self.color),
+            background: other.background.or(self.background),
+            bold: other.bold.or(self.bold),
+            size: other.size.or(self.size),
+        }
+    }
+
+    // Styles are applied to the ui the element is rendered into, so they cascade to children
+    pub fn apply(&self, ui: &mut egui::Ui) {
+        if let Some(size) = self.size {
+            ui.style_mut().override_font_id = Some(egui::FontId::proportional(size));
+        }
+        let visuals = ui.visuals_mut();
+        if let Some(color) = self.color {
+            visuals.override_text_color = Some(color);
+        } else if self.bold == Some(true) {
+            visuals.override_text_color = Some(visuals.strong_text_color());
+        }
+        if let Some(background) = self.background {
+            visuals.widgets.inactive.weak_bg_fill = background;
+            visuals.widgets.inactive.bg_fill = background;
+        }
+    }
+}
+
+// A rule's class, and the scope id (`data-v-…`) of the component it's scoped to
+type RuleKey = (String, Option<String>);
+
+// `.danger` or, for a scoped rule, `.danger[data-v-7ba5bd90]`
+fn parse_selector(selector: &str) -> RuleKey {
+    let selector = selector.trim().trim_start_matches('.');
+    match selector.split_once('[') {
+        Some((class, scope)) => (
+            class.to_string(),
+            Some(scope.trim_end_matches(']').trim().to_string()),
+        ),
+        None => (selector.to_string(), None),
+    }
+}
+
+// The `vgui.styles` object, a global stylesheet of class rules, rules can be scoped to the
+// elements of one component like `<style scoped>` does
+pub struct Styles {
+    object: Object,
+    rules: Rc<RefCell<HashMap<RuleKey, Style>>>,
+}
+
+impl Styles {
+    pub fn new(isolate: &MiniV8) -> Self {
+        let object = isolate.create_object();
+        let rules = Rc::new(RefCell::new(HashMap::new()));
+
+        // register({ '.danger': { color: '#f55', bold: true }, '.title[data-v-1]': { size: 18 } })
+        let rules_clone = rules.clone();
+        let register = isolate.create_function(move |invocation| {
+            let (sheet,): (Object,) = invocation.args.into(&invocation.mv8)?;
+            let mut rules = rules_clone.borrow_mut();
+            for property in sheet.properties::<String, Object>(false)? {
+                let (selector, rule) = property?;
+                let style = Style::from_object(&invocation.mv8, rule)?;
+                rules.insert(parse_selector(&selector), style);
+            }
+            Ok(())
+        });
+        object
+            .set("register", register)
+            .expect("Failed to set styles.register");
+
+        Self { object, rules }
+    }
+
+    pub fn object(&self) -> Object {
+        self.object.clone()
+    }
+
+    // Merges the rules of all classes in order, unknown classes are ignored. Scoped rules
+    // only match elements carrying their scope id, and win over the class's global rule
+    pub fn resolve(&self, classes: &[String], scope_ids: &[String]) -> Option<Style> {
+        let rules = self.rules.borrow();
+        classes
+            .iter()
+            .flat_map(|class| {
+                std::iter::once(None)
+                    .chain(scope_ids.iter().map(Some))
+                    .filter_map(|scope_id| rules.get(&(class.clone(), scope_id.cloned())).copied())
+                    .collect::<Vec<_>>()
+            })
+            .reduce(Style::merge)
+    }
+}
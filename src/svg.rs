@@ -0,0 +1,96 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use eframe::egui;
+
+// Content hash, pixel size (0 for a side that isn't given) and the pixels per point it was
+// rasterized at
+type TextureKey = (u64, [u32; 2], u32);
+
+// A cache entry and the frame it was last drawn in
+struct Used<T> {
+    value: T,
+    frame: u64,
+}
+
+// Rasterized SVG textures, keyed by content hash, pixel size and scale
+// Sources starting with `<` are inline SVG markup, anything else is a file path. Entries that
+// weren't drawn in the previous frame are dropped, so changing markup or zooming doesn't pile
+// up textures
+#[derive(Default)]
+pub struct SvgCache {
+    files: HashMap<String, Used<Result<Vec<u8>, String>>>,
+    textures: HashMap<TextureKey, Used<Result<egui::TextureHandle, String>>>,
+    frame: u64,
+}
+
+impl SvgCache {
+    // Runs on the first draw of a frame
+    fn evict(&mut self, frame: u64) {
+        let is_recent = |used: u64| used + 1 >= frame;
+        self.files.retain(|_, file| is_recent(file.frame));
+        self.textures.retain(|_, texture| is_recent(texture.frame));
+        self.frame = frame;
+    }
+
+    // `width` and `height` are in points, the SVG is rasterized at the current pixels per point,
+    // without either at its own size scaled to pixels
+    pub fn get(
+        &mut self,
+        ctx: &egui::Context,
+        src: &str,
+        width: Option<f32>,
+        height: Option<f32>,
+    ) -> Result<egui::TextureHandle, String> {
+        let frame = ctx.frame_nr();
+        if frame != self.frame {
+            self.evict(frame);
+        }
+
+        let bytes = if src.trim_start().starts_with('<') {
+            src.as_bytes()
+        } else {
+            let file = self.files.entry(src.to_string()).or_insert_with(|| Used {
+                value: std::fs::read(src).map_err(|e| e.to_string()),
+                frame,
+            });
+            file.frame = frame;
+            match &file.value {
+                Ok(bytes) => bytes.as_slice(),
+                Err(error) => return Err(error.clone()),
+            }
+        };
+
+        let pixels_per_point = ctx.pixels_per_point();
+        let to_pixels = |size: f32| (size * pixels_per_point).round() as u32;
+        let size_hint = match (width, height) {
+            (Some(width), Some(height)) => {
+                Some(egui::SizeHint::Size(to_pixels(width), to_pixels(height)))
+            }
+            (Some(width), None) => Some(egui::SizeHint::Width(to_pixels(width))),
+            (None, Some(height)) => Some(egui::SizeHint::Height(to_pixels(height))),
+            (None, None) => Some(egui::SizeHint::Scale(egui::emath::OrderedFloat(
+                pixels_per_point,
+            ))),
+        };
+
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        let key = (
+            hasher.finish(),
+            [width.map_or(0, to_pixels), height.map_or(0, to_pixels)],
+            pixels_per_point.to_bits(),
+        );
+
+        let texture = self.textures.entry(key).or_insert_with(|| Used {
+            value: egui_extras::image::load_svg_bytes_with_size(bytes, size_hint).map(|image| {
+                ctx.load_texture(format!("svg-{:x}", key.0), image, Default::default())
+            }),
+            frame,
+        });
+        texture.frame = frame;
+        texture.value.clone()
+    }
+}
@@ -0,0 +1,217 @@
+use mini_v8::{Function, MiniV8};
+
+// `TextEncoder` and `TextDecoder` (UTF-8 only) on top of `encodeInto(string, capacity)` and
+// `decode(bytes, fatal, stripBom, stream)`, bytes cross over as plain arrays
+const PRELUDE: &str = r#"
+(encoding) => {
+    const labels = ['utf-8', 'utf8', 'unicode-1-1-utf-8', 'unicode11utf8', 'unicode20utf8',
+        'x-unicode20utf8'];
+    const toBytes = (input) => {
+        if (input === undefined) {
+            return [];
+        }
+        if (input instanceof ArrayBuffer) {
+            return Array.from(new Uint8Array(input));
+        }
+        if (ArrayBuffer.isView(input)) {
+            return Array.from(new Uint8Array(input.buffer, input.byteOffset, input.byteLength));
+        }
+        throw new TypeError('The provided value is not an ArrayBuffer or an ArrayBufferView');
+    };
+
+    class TextEncoder {
+        get encoding() {
+            return 'utf-8';
+        }
+        encode(input = '') {
+            return new Uint8Array(encoding.encodeInto(String(input), Infinity)[0]);
+        }
+        encodeInto(input, destination) {
+            const [bytes, read] = encoding.encodeInto(String(input), destination.length);
+            destination.set(bytes);
+            return { read, written: bytes.length };
+        }
+    }
+
+    class TextDecoder {
+        constructor(label = 'utf-8', options = {}) {
+            if (!labels.includes(String(label).trim().toLowerCase())) {
+                throw new RangeError(`The encoding label provided ('${label}') is invalid`);
+            }
+            this._fatal = Boolean(options.fatal);
+            this._ignoreBOM = Boolean(options.ignoreBOM);
+            this._pending = [];
+            this._started = false;
+        }
+        get encoding() {
+            return 'utf-8';
+        }
+        get fatal() {
+            return this._fatal;
+        }
+        get ignoreBOM() {
+            return this._ignoreBOM;
+        }
+        decode(input, options = {}) {
+            const stream = Boolean(options.stream);
+            const bytes = this._pending.concat(toBytes(input));
+            const decoded = encoding.decode(bytes, this._fatal,
+                !this._ignoreBOM && !this._started, stream);
+            if (decoded === null) {
+                this._pending = [];
+                this._started = false;
+                throw new TypeError('The encoded data was not valid for encoding utf-8');
+            }
+            const [text, consumed] = decoded;
+            this._pending = bytes.slice(consumed);
+            // a new stream starts after every call that isn't streaming
+            this._started = stream && (this._started || consumed > 0);
+            return text;
+        }
+    }
+
+    globalThis.TextEncoder = TextEncoder;
+    globalThis.TextDecoder = TextDecoder;
+}
+"#;
+
+// The UTF-8 of as much of `text` as fits into `capacity` bytes without splitting a character,
+// and how many UTF-16 code units of `text` that covers
+fn encode_into(text: &str, capacity: usize) -> (Vec<u8>, usize) {
+    let mut bytes = Vec::with_capacity(text.len().min(capacity));
+    let mut read = 0;
+    for c in text.chars() {
+        if bytes.len() + c.len_utf8() > capacity {
+            break;
+        }
+        let mut buffer = [0; 4];
+        bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+        read += c.len_utf16();
+    }
+    (bytes, read)
+}
+
+// The length of the character cut off at the end of `bytes`, kept for the next chunk of a stream
+fn incomplete_tail(bytes: &[u8]) -> usize {
+    let is_continuation = |byte: u8| byte & 0b1100_0000 == 0b1000_0000;
+    for tail in 1..=bytes.len().min(3) {
+        let lead = bytes[bytes.len() - tail];
+        if is_continuation(lead) {
+            continue;
+        }
+        let length = match lead {
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => return 0,
+        };
+        return if length > tail { tail } else { 0 };
+    }
+    0
+}
+
+// Decodes UTF-8, invalid sequences become U+FFFD unless `fatal`. Returns the text and how many
+// bytes it used, a `stream` leaves a character that's cut off at the end for the next chunk
+fn decode(bytes: &[u8], fatal: bool, strip_bom: bool, stream: bool) -> Option<(String, usize)> {
+    let end = if stream {
+        bytes.len() - incomplete_tail(bytes)
+    } else {
+        bytes.len()
+    };
+    let mut complete = &bytes[..end];
+    if strip_bom {
+        complete = complete.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(complete);
+    }
+    let text = if fatal {
+        std::str::from_utf8(complete).ok()?.to_string()
+    } else {
+        String::from_utf8_lossy(complete).into_owned()
+    };
+    Some((text, end))
+}
+
+pub fn register(isolate: &MiniV8) {
+    let encoding = isolate.create_object();
+
+    // encodeInto(string, capacity) returns [bytes, read]
+    let encode = isolate.create_function(|invocation| {
+        let (text, capacity): (String, f64) = invocation.args.into(&invocation.mv8)?;
+        let capacity = if capacity.is_finite() {
+            capacity.max(0.0) as usize
+        } else {
+            usize::MAX
+        };
+        let (bytes, read) = encode_into(&text, capacity);
+        let result = invocation.mv8.create_array();
+        result.push(bytes)?;
+        result.push(read)?;
+        Ok(result)
+    });
+    encoding
+        .set("encodeInto", encode)
+        .expect("Failed to set encodeInto");
+
+    // decode(bytes, fatal, stripBom, stream) returns [text, consumed], or null for invalid UTF-8
+    // when fatal
+    let decode = isolate.create_function(|invocation| {
+        let (bytes, fatal, strip_bom, stream): (Vec<u8>, bool, bool, bool) =
+            invocation.args.into(&invocation.mv8)?;
+        let Some((text, consumed)) = decode(&bytes, fatal, strip_bom, stream) else {
+            return Ok(None);
+        };
+        let result = invocation.mv8.create_array();
+        result.push(text)?;
+        result.push(consumed)?;
+        Ok(Some(result))
+    });
+    encoding
+        .set("decode", decode)
+        .expect("Failed to set decode");
+
+    isolate
+        .eval::<_, Function>(PRELUDE)
+        .and_then(|prelude| prelude.call::<_, ()>((encoding,)))
+        .expect("Failed to set TextEncoder and TextDecoder");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_whole_characters_only() {
+        assert_eq!(
+            encode_into("aé😀", usize::MAX),
+            ("aé😀".as_bytes().to_vec(), 4)
+        );
+        // the emoji needs 4 bytes but only 3 are left
+        assert_eq!(encode_into("aé😀", 6), ("aé".as_bytes().to_vec(), 2));
+        assert_eq!(encode_into("é", 1), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn decodes_streams_across_chunks() {
+        let bytes = "h€llo".as_bytes();
+        // the euro sign is split after its first byte
+        let (text, consumed) = decode(&bytes[..2], false, true, true).unwrap();
+        assert_eq!((text.as_str(), consumed), ("h", 1));
+        let (text, consumed) = decode(&bytes[1..], false, false, true).unwrap();
+        assert_eq!((text.as_str(), consumed), ("€llo", 6));
+    }
+
+    #[test]
+    fn handles_boms_and_invalid_bytes() {
+        let bytes = b"\xEF\xBB\xBFok\xFF";
+        assert_eq!(decode(bytes, false, true, false).unwrap().0, "ok\u{FFFD}");
+        assert_eq!(
+            decode(bytes, false, false, false).unwrap().0,
+            "\u{FEFF}ok\u{FFFD}"
+        );
+        assert!(decode(bytes, true, true, false).is_none());
+        // a cut off character is invalid once the stream ends
+        assert_eq!(
+            decode(b"a\xE2\x82", false, true, false).unwrap().0,
+            "a\u{FFFD}"
+        );
+    }
+}
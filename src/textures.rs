@@ -0,0 +1,96 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use eframe::egui;
+use eyre::{Context, Result};
+
+enum TextureState {
+    Loading,
+    Decoded(egui::ColorImage),
+    Loaded(egui::TextureHandle),
+    Failed(String),
+}
+
+pub enum TextureStatus {
+    Loading,
+    Ready(egui::TextureHandle),
+    Failed(String),
+}
+
+// Texture registry keyed by source (file path or URL)
+// Images are loaded and decoded on the tokio runtime, then uploaded to egui on the UI thread
+#[derive(Clone, Default)]
+pub struct TextureRegistry {
+    textures: Arc<Mutex<HashMap<String, TextureState>>>,
+}
+
+impl TextureRegistry {
+    pub fn get(&self, ctx: &egui::Context, src: &str) -> TextureStatus {
+        let mut textures = self.textures.lock().expect("Failed to lock textures");
+        let state = textures.entry(src.to_string()).or_insert_with(|| {
+            self.spawn_load(ctx, src);
+            TextureState::Loading
+        });
+
+        // upload freshly decoded images, this has to happen on the UI thread
+        if let TextureState::Decoded(image) = state {
+            let image = std::mem::take(image);
+            *state = TextureState::Loaded(ctx.load_texture(src, image, Default::default()));
+        }
+
+        match state {
+            TextureState::Loading | TextureState::Decoded(_) => TextureStatus::Loading,
+            TextureState::Loaded(texture) => TextureStatus::Ready(texture.clone()),
+            TextureState::Failed(error) => TextureStatus::Failed(error.clone()),
+        }
+    }
+
+    fn spawn_load(&self, ctx: &egui::Context, src: &str) {
+        let textures = self.textures.clone();
+        let ctx = ctx.clone();
+        let src = src.to_string();
+        tokio::spawn(async move {
+            let state = match load_image(&src).await {
+                Ok(image) => TextureState::Decoded(image),
+                Err(e) => {
+                    println!("Failed to load image {}: {:?}", src, e);
+                    TextureState::Failed(e.to_string())
+                }
+            };
+            textures
+                .lock()
+                .expect("Failed to lock textures")
+                .insert(src, state);
+            ctx.request_repaint();
+        });
+    }
+}
+
+async fn load_image(src: &str) -> Result<egui::ColorImage> {
+    let bytes = if src.starts_with("http://") || src.starts_with("https://") {
+        reqwest::get(src)
+            .await
+            .wrap_err("Failed to request image")?
+            .error_for_status()
+            .wrap_err("Failed to request image")?
+            .bytes()
+            .await
+            .wrap_err("Failed to read image response")?
+            .to_vec()
+    } else {
+        tokio::fs::read(src)
+            .await
+            .wrap_err_with(|| format!("Failed to read image file: {}", src))?
+    };
+
+    let image = image::load_from_memory(&bytes)
+        .wrap_err("Failed to decode image")?
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        image.as_flat_samples().as_slice(),
+    ))
+}
@@ -0,0 +1,153 @@
+use std::{cell::RefCell, rc::Rc};
+
+use eframe::egui;
+use mini_v8::{Error as MiniV8Error, MiniV8, Object, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThemeMode {
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    fn name(self) -> &'static str {
+        match self {
+            ThemeMode::Dark => "dark",
+            ThemeMode::Light => "light",
+        }
+    }
+
+    fn visuals(self) -> egui::Visuals {
+        match self {
+            ThemeMode::Dark => egui::Visuals::dark(),
+            ThemeMode::Light => egui::Visuals::light(),
+        }
+    }
+}
+
+enum ThemeChange {
+    Mode(ThemeMode),
+    // Partial visuals, merged into the current ones
+    Visuals(serde_json::Value),
+}
+
+struct ThemeState {
+    mode: ThemeMode,
+    pending: Vec<ThemeChange>,
+}
+
+// Recursively overrides the fields of `base` with the ones present in `patch`
+fn merge_json(base: &mut serde_json::Value, patch: serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(base_value) => merge_json(base_value, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+fn patch_visuals(
+    visuals: &egui::Visuals,
+    patch: serde_json::Value,
+) -> Result<egui::Visuals, serde_json::Error> {
+    let mut merged = serde_json::to_value(visuals)?;
+    merge_json(&mut merged, patch);
+    serde_json::from_value(merged)
+}
+
+// The `vgui.theme` object, scripts only record the requested changes
+// and they are applied to the context at the start of the next frame
+pub struct Theme {
+    object: Object,
+    state: Rc<RefCell<ThemeState>>,
+}
+
+impl Theme {
+    pub fn new(isolate: &MiniV8) -> Self {
+        let object = isolate.create_object();
+        let state = Rc::new(RefCell::new(ThemeState {
+            mode: ThemeMode::Dark,
+            pending: Vec::new(),
+        }));
+
+        // set('dark' | 'light')
+        let state_clone = state.clone();
+        let set = isolate.create_function(move |invocation| {
+            let (mode,): (String,) = invocation.args.into(&invocation.mv8)?;
+            let mode = match mode.as_str() {
+                "dark" => ThemeMode::Dark,
+                "light" => ThemeMode::Light,
+                _ => {
+                    return Err(MiniV8Error::ExternalError(
+                        format!("Unknown theme: {}", mode).into(),
+                    ))
+                }
+            };
+            let mut state = state_clone.borrow_mut();
+            state.mode = mode;
+            state.pending.push(ThemeChange::Mode(mode));
+            Ok(())
+        });
+        object.set("set", set).expect("Failed to set theme.set");
+
+        // apply({ panel_fill: [30, 30, 30, 255], widgets: { ... } }), an object or a JSON string
+        // using the field names of egui's `Visuals`
+        let state_clone = state.clone();
+        let apply = isolate.create_function(move |invocation| {
+            let (visuals,): (Value,) = invocation.args.into(&invocation.mv8)?;
+            let json: String = if visuals.is_string() {
+                visuals.into(&invocation.mv8)?
+            } else {
+                let json: Object = invocation.mv8.global().get("JSON")?;
+                json.call_prop("stringify", (visuals,))?
+            };
+            let patch: serde_json::Value = serde_json::from_str(&json).map_err(|e| {
+                MiniV8Error::ExternalError(format!("Invalid visuals: {}", e).into())
+            })?;
+            // validate against the default visuals, so mistakes are reported to the caller
+            patch_visuals(&egui::Visuals::default(), patch.clone()).map_err(|e| {
+                MiniV8Error::ExternalError(format!("Invalid visuals: {}", e).into())
+            })?;
+            state_clone
+                .borrow_mut()
+                .pending
+                .push(ThemeChange::Visuals(patch));
+            Ok(())
+        });
+        object
+            .set("apply", apply)
+            .expect("Failed to set theme.apply");
+
+        // get() -> 'dark' | 'light'
+        let state_clone = state.clone();
+        let get =
+            isolate.create_function(move |_| Ok(state_clone.borrow().mode.name().to_string()));
+        object.set("get", get).expect("Failed to set theme.get");
+
+        Self { object, state }
+    }
+
+    pub fn object(&self) -> Object {
+        self.object.clone()
+    }
+
+    pub fn apply(&self, ctx: &egui::Context) {
+        let mut state = self.state.borrow_mut();
+        for change in state.pending.drain(..) {
+            match change {
+                ThemeChange::Mode(mode) => ctx.set_visuals(mode.visuals()),
+                ThemeChange::Visuals(patch) => match patch_visuals(&ctx.style().visuals, patch) {
+                    Ok(visuals) => ctx.set_visuals(visuals),
+                    Err(e) => println!("Failed to apply visuals: {}", e),
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap, HashSet},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use eframe::egui;
+use mini_v8::{Error as MiniV8Error, Function, MiniV8, Value};
+
+use crate::event::call_handler;
+
+struct Timer {
+    callback: Function,
+    args: Vec<Value>,
+    deadline: Instant,
+    // intervals are rescheduled after firing
+    interval: Option<Duration>,
+}
+
+#[derive(Default)]
+struct TimerQueue {
+    next_id: u32,
+    timers: HashMap<u32, Timer>,
+    // ordered by deadline, ties fire in the order they were scheduled
+    deadlines: BTreeSet<(Instant, u32)>,
+}
+
+impl TimerQueue {
+    fn schedule(&mut self, id: u32, timer: Timer) {
+        self.deadlines.insert((timer.deadline, id));
+        self.timers.insert(id, timer);
+    }
+
+    fn cancel(&mut self, id: u32) {
+        if let Some(timer) = self.timers.remove(&id) {
+            self.deadlines.remove(&(timer.deadline, id));
+        }
+    }
+}
+
+// `setTimeout`, `setInterval`, `clearTimeout` and `clearInterval`, the timers are serviced
+// from the update loop, which sleeps until the next one is due
+pub struct Timers {
+    queue: Rc<RefCell<TimerQueue>>,
+}
+
+fn delay(value: Option<f64>) -> Duration {
+    Duration::from_secs_f64(
+        value
+            .filter(|delay| delay.is_finite())
+            .unwrap_or(0.0)
+            .max(0.0)
+            / 1000.0,
+    )
+}
+
+impl Timers {
+    pub fn new(isolate: &MiniV8) -> Self {
+        let queue = Rc::new(RefCell::new(TimerQueue::default()));
+
+        // setTimeout(callback, delay, ...args) and setInterval(callback, delay, ...args)
+        for (name, repeat) in [("setTimeout", false), ("setInterval", true)] {
+            let queue_clone = queue.clone();
+            let set = isolate.create_function(move |invocation| {
+                let mut args = invocation.args.into_vec();
+                if args.is_empty() {
+                    return Err(MiniV8Error::ExternalError(
+                        format!("{} expects a callback", name).into(),
+                    ));
+                }
+                let callback = match args.remove(0) {
+                    Value::Function(callback) => callback,
+                    _ => {
+                        return Err(MiniV8Error::ExternalError(
+                            format!("{} expects a function, not a string of code", name).into(),
+                        ))
+                    }
+                };
+                let delay = delay(if args.is_empty() {
+                    None
+                } else {
+                    args.remove(0).into(&invocation.mv8)?
+                });
+
+                let mut queue = queue_clone.borrow_mut();
+                queue.next_id += 1;
+                let id = queue.next_id;
+                queue.schedule(
+                    id,
+                    Timer {
+                        callback,
+                        args,
+                        deadline: Instant::now() + delay,
+                        interval: repeat.then_some(delay),
+                    },
+                );
+                Ok(id)
+            });
+            isolate
+                .global()
+                .set(name, set)
+                .unwrap_or_else(|_| panic!("Failed to set {}", name));
+        }
+
+        // clearTimeout(id) and clearInterval(id), timeouts and intervals share their ids
+        for name in ["clearTimeout", "clearInterval"] {
+            let queue_clone = queue.clone();
+            let clear = isolate.create_function(move |invocation| {
+                let (id,): (Option<u32>,) = invocation.args.into(&invocation.mv8)?;
+                if let Some(id) = id {
+                    queue_clone.borrow_mut().cancel(id);
+                }
+                Ok(())
+            });
+            isolate
+                .global()
+                .set(name, clear)
+                .unwrap_or_else(|_| panic!("Failed to set {}", name));
+        }
+
+        Self { queue }
+    }
+
+    // Fires the timers that are due, timers scheduled by them wait for the next run even when
+    // their delay is 0, so a timer rescheduling itself can't hang the frame
+    pub fn run(&self, isolate: &MiniV8, ctx: &egui::Context) {
+        let now = Instant::now();
+        let due: Vec<u32> = self
+            .queue
+            .borrow()
+            .deadlines
+            .iter()
+            .take_while(|(deadline, _)| *deadline <= now)
+            .map(|(_, id)| *id)
+            .collect();
+
+        for id in due {
+            let fired = {
+                let mut queue = self.queue.borrow_mut();
+                let Some(timer) = queue.timers.remove(&id) else {
+                    // cleared by a timer that fired before it
+                    continue;
+                };
+                queue.deadlines.remove(&(timer.deadline, id));
+                let fired = (timer.callback.clone(), timer.args.clone());
+                if let Some(interval) = timer.interval {
+                    // an interval that fell behind skips the ticks it missed
+                    let deadline = (timer.deadline + interval).max(now);
+                    queue.schedule(id, Timer { deadline, ..timer });
+                }
+                fired
+            };
+            let (callback, args) = fired;
+            call_handler(isolate, &callback, "timer", args);
+        }
+
+        if let Some((deadline, _)) = self.queue.borrow().deadlines.first() {
+            ctx.request_repaint_after(deadline.saturating_duration_since(Instant::now()));
+        }
+    }
+}
+
+#[derive(Default)]
+struct FrameCallbacks {
+    next_id: u32,
+    // in the order they were requested
+    callbacks: Vec<(u32, Function)>,
+    // cancelled while the current frame's callbacks run
+    cancelled: HashSet<u32>,
+}
+
+// `requestAnimationFrame` and `cancelAnimationFrame`, callbacks get the frame's timestamp in
+// milliseconds since `origin` and keep frames coming while any are pending
+pub struct AnimationFrames {
+    pending: Rc<RefCell<FrameCallbacks>>,
+    origin: Instant,
+}
+
+impl AnimationFrames {
+    pub fn new(isolate: &MiniV8, origin: Instant) -> Self {
+        let pending = Rc::new(RefCell::new(FrameCallbacks::default()));
+
+        // requestAnimationFrame(callback)
+        let pending_clone = pending.clone();
+        let request = isolate.create_function(move |invocation| {
+            let (callback,): (Function,) = invocation.args.into(&invocation.mv8)?;
+            let mut pending = pending_clone.borrow_mut();
+            pending.next_id += 1;
+            let id = pending.next_id;
+            pending.callbacks.push((id, callback));
+            Ok(id)
+        });
+        isolate
+            .global()
+            .set("requestAnimationFrame", request)
+            .expect("Failed to set requestAnimationFrame");
+
+        // cancelAnimationFrame(id)
+        let pending_clone = pending.clone();
+        let cancel = isolate.create_function(move |invocation| {
+            let (id,): (Option<u32>,) = invocation.args.into(&invocation.mv8)?;
+            if let Some(id) = id {
+                let mut pending = pending_clone.borrow_mut();
+                pending
+                    .callbacks
+                    .retain(|(callback_id, _)| *callback_id != id);
+                pending.cancelled.insert(id);
+            }
+            Ok(())
+        });
+        isolate
+            .global()
+            .set("cancelAnimationFrame", cancel)
+            .expect("Failed to set cancelAnimationFrame");
+
+        Self { pending, origin }
+    }
+
+    // Runs the callbacks requested before this frame, all with the same timestamp, the ones
+    // they request in turn run on the next frame
+    pub fn run(&self, isolate: &MiniV8, ctx: &egui::Context) {
+        let callbacks = {
+            let mut pending = self.pending.borrow_mut();
+            pending.cancelled.clear();
+            std::mem::take(&mut pending.callbacks)
+        };
+        let timestamp = self.origin.elapsed().as_secs_f64() * 1000.0;
+        for (id, callback) in callbacks {
+            // cancelled by a callback that ran before it
+            if self.pending.borrow_mut().cancelled.remove(&id) {
+                continue;
+            }
+            call_handler(
+                isolate,
+                &callback,
+                "animationFrame",
+                vec![Value::Number(timestamp)],
+            );
+        }
+
+        if !self.pending.borrow().callbacks.is_empty() {
+            ctx.request_repaint();
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{ElementId, ElementsChildren};
+
+// The tree operations behind Vue's node ops, they follow the DOM so fragments work: a fragment
+// is a run of siblings between two empty text anchors, its children are inserted before the
+// end anchor and it's moved and removed by walking `next_sibling` from the start anchor
+
+// Inserts `child` into `parent` before `anchor`, or last without one. An already attached
+// child is moved, like `insertBefore` does
+pub fn insert(
+    children: &mut ElementsChildren,
+    child: ElementId,
+    parent: ElementId,
+    anchor: Option<ElementId>,
+) -> Result<(), String> {
+    detach(children, child);
+    let parent_children = children.entry(parent).or_default();
+    let index = match anchor {
+        Some(anchor) => parent_children
+            .iter()
+            .position(|id| *id == anchor)
+            .ok_or_else(|| format!("Anchor {} is not a child of {}", anchor, parent))?,
+        None => parent_children.len(),
+    };
+    parent_children.insert(index, child);
+    Ok(())
+}
+
+// Takes `child` out of its parent, returns that parent, `None` when it wasn't attached
+pub fn detach(children: &mut ElementsChildren, child: ElementId) -> Option<ElementId> {
+    children.iter_mut().find_map(|(parent, parent_children)| {
+        let index = parent_children.iter().position(|id| *id == child)?;
+        parent_children.remove(index);
+        Some(*parent)
+    })
+}
+
+pub fn parent(children: &ElementsChildren, node: ElementId) -> Option<ElementId> {
+    children
+        .iter()
+        .find(|(_, parent_children)| parent_children.contains(&node))
+        .map(|(parent, _)| *parent)
+}
+
+pub fn next_sibling(children: &ElementsChildren, node: ElementId) -> Option<ElementId> {
+    children.values().find_map(|parent_children| {
+        let index = parent_children.iter().position(|id| *id == node)?;
+        parent_children.get(index + 1).copied()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn children_of(children: &ElementsChildren, parent: ElementId) -> Vec<ElementId> {
+        children.get(&parent).cloned().unwrap_or_default()
+    }
+
+    // Mounts a fragment the way Vue does: both anchors at `anchor`, then the children before
+    // the end anchor
+    fn mount_fragment(
+        children: &mut ElementsChildren,
+        parent: ElementId,
+        start: ElementId,
+        end: ElementId,
+        fragment_children: &[ElementId],
+        anchor: Option<ElementId>,
+    ) {
+        insert(children, start, parent, anchor).unwrap();
+        insert(children, end, parent, anchor).unwrap();
+        for child in fragment_children {
+            insert(children, *child, parent, Some(end)).unwrap();
+        }
+    }
+
+    // Removes a fragment the way Vue does, walking the siblings from the start anchor
+    fn remove_fragment(children: &mut ElementsChildren, start: ElementId, end: ElementId) {
+        let mut node = start;
+        while node != end {
+            let next = next_sibling(children, node).unwrap();
+            detach(children, node);
+            node = next;
+        }
+        detach(children, end);
+    }
+
+    #[test]
+    fn insert_appends_without_anchor() {
+        let mut children = ElementsChildren::new();
+        insert(&mut children, 1, 0, None).unwrap();
+        insert(&mut children, 2, 0, None).unwrap();
+        assert_eq!(children_of(&children, 0), vec![1, 2]);
+    }
+
+    #[test]
+    fn insert_before_anchor() {
+        let mut children = ElementsChildren::new();
+        insert(&mut children, 1, 0, None).unwrap();
+        insert(&mut children, 2, 0, Some(1)).unwrap();
+        assert_eq!(children_of(&children, 0), vec![2, 1]);
+    }
+
+    #[test]
+    fn insert_with_foreign_anchor_fails() {
+        let mut children = ElementsChildren::new();
+        insert(&mut children, 1, 0, None).unwrap();
+        insert(&mut children, 2, 1, None).unwrap();
+        assert!(insert(&mut children, 3, 0, Some(2)).is_err());
+    }
+
+    #[test]
+    fn insert_moves_attached_child() {
+        let mut children = ElementsChildren::new();
+        insert(&mut children, 1, 0, None).unwrap();
+        insert(&mut children, 2, 0, None).unwrap();
+        insert(&mut children, 3, 0, None).unwrap();
+        insert(&mut children, 3, 0, Some(1)).unwrap();
+        assert_eq!(children_of(&children, 0), vec![3, 1, 2]);
+
+        insert(&mut children, 2, 1, None).unwrap();
+        assert_eq!(children_of(&children, 0), vec![3, 1]);
+        assert_eq!(children_of(&children, 1), vec![2]);
+    }
+
+    #[test]
+    fn fragment_children_stay_between_anchors() {
+        let mut children = ElementsChildren::new();
+        insert(&mut children, 1, 0, None).unwrap();
+        mount_fragment(&mut children, 0, 10, 11, &[12, 13, 14], None);
+        insert(&mut children, 2, 0, None).unwrap();
+        assert_eq!(children_of(&children, 0), vec![1, 10, 12, 13, 14, 11, 2]);
+
+        // a child added to the fragment later still goes before its end anchor
+        insert(&mut children, 15, 0, Some(11)).unwrap();
+        assert_eq!(
+            children_of(&children, 0),
+            vec![1, 10, 12, 13, 14, 15, 11, 2]
+        );
+    }
+
+    #[test]
+    fn fragment_mounted_before_sibling() {
+        let mut children = ElementsChildren::new();
+        insert(&mut children, 1, 0, None).unwrap();
+        insert(&mut children, 2, 0, None).unwrap();
+        // e.g. a multi-root component toggled on with `v-if` between two elements
+        mount_fragment(&mut children, 0, 10, 11, &[12, 13], Some(2));
+        assert_eq!(children_of(&children, 0), vec![1, 10, 12, 13, 11, 2]);
+    }
+
+    #[test]
+    fn nested_fragments() {
+        let mut children = ElementsChildren::new();
+        mount_fragment(&mut children, 0, 10, 11, &[12], None);
+        mount_fragment(&mut children, 0, 20, 21, &[22, 23], Some(11));
+        assert_eq!(children_of(&children, 0), vec![10, 12, 20, 22, 23, 21, 11]);
+    }
+
+    #[test]
+    fn fragment_moves_as_a_whole() {
+        let mut children = ElementsChildren::new();
+        mount_fragment(&mut children, 0, 10, 11, &[12, 13], None);
+        mount_fragment(&mut children, 0, 20, 21, &[22], None);
+
+        // keyed fragments are moved node by node, from the start anchor to the end anchor
+        for node in [20, 22, 21] {
+            insert(&mut children, node, 0, Some(10)).unwrap();
+        }
+        assert_eq!(children_of(&children, 0), vec![20, 22, 21, 10, 12, 13, 11]);
+    }
+
+    #[test]
+    fn fragment_removal_leaves_siblings() {
+        let mut children = ElementsChildren::new();
+        insert(&mut children, 1, 0, None).unwrap();
+        mount_fragment(&mut children, 0, 10, 11, &[12, 13], None);
+        insert(&mut children, 2, 0, None).unwrap();
+
+        remove_fragment(&mut children, 10, 11);
+        assert_eq!(children_of(&children, 0), vec![1, 2]);
+    }
+
+    #[test]
+    fn parent_and_next_sibling() {
+        let mut children = ElementsChildren::new();
+        mount_fragment(&mut children, 0, 10, 11, &[12], None);
+        assert_eq!(parent(&children, 12), Some(0));
+        assert_eq!(parent(&children, 0), None);
+        assert_eq!(next_sibling(&children, 10), Some(12));
+        assert_eq!(next_sibling(&children, 11), None);
+    }
+
+    #[test]
+    fn detach_unattached_child() {
+        let mut children = ElementsChildren::new();
+        assert_eq!(detach(&mut children, 1), None);
+    }
+}
@@ -0,0 +1,310 @@
+use ::url::{form_urlencoded, quirks, Url};
+use mini_v8::{Function, MiniV8, Object};
+
+// `URL` keeps the parsed components and goes back to the `url` crate for every change, its
+// `searchParams` writes the query back through `_setQuery`
+const PRELUDE: &str = r#"
+(native) => {
+    const baseOf = (base) => (base === undefined ? null : String(base));
+
+    class URLSearchParams {
+        // a query string, [name, value] pairs, a record or another URLSearchParams
+        constructor(init = '') {
+            this._url = null;
+            if (init instanceof URLSearchParams) {
+                this._list = init._list.map(([name, value]) => [name, value]);
+            } else if (init !== null && typeof init === 'object') {
+                if (typeof init[Symbol.iterator] === 'function') {
+                    this._list = Array.from(init, (pair) => {
+                        const [name, value, ...rest] = Array.from(pair);
+                        if (value === undefined || rest.length > 0) {
+                            throw new TypeError('Each query pair must be a [name, value] pair');
+                        }
+                        return [String(name), String(value)];
+                    });
+                } else {
+                    this._list = Object.keys(init).map((name) => [name, String(init[name])]);
+                }
+            } else {
+                this._list = native.parseQuery(String(init));
+            }
+        }
+        get size() {
+            return this._list.length;
+        }
+        append(name, value) {
+            this._list.push([String(name), String(value)]);
+            this._update();
+        }
+        delete(name, value) {
+            this._list = this._list.filter(([n, v]) =>
+                n !== String(name) || (value !== undefined && v !== String(value)));
+            this._update();
+        }
+        get(name) {
+            const pair = this._list.find(([n]) => n === String(name));
+            return pair ? pair[1] : null;
+        }
+        getAll(name) {
+            return this._list.filter(([n]) => n === String(name)).map(([, value]) => value);
+        }
+        has(name, value) {
+            return this._list.some(([n, v]) =>
+                n === String(name) && (value === undefined || v === String(value)));
+        }
+        // replaces the first pair with the name and drops the others
+        set(name, value) {
+            name = String(name);
+            const index = this._list.findIndex(([n]) => n === name);
+            if (index === -1) {
+                this._list.push([name, String(value)]);
+            } else {
+                this._list[index] = [name, String(value)];
+                this._list = this._list.filter(([n], i) => n !== name || i <= index);
+            }
+            this._update();
+        }
+        sort() {
+            this._list.sort(([a], [b]) => (a < b ? -1 : a > b ? 1 : 0));
+            this._update();
+        }
+        forEach(callback, thisArg) {
+            for (const [name, value] of this) {
+                callback.call(thisArg, value, name, this);
+            }
+        }
+        entries() {
+            return this._list.map(([name, value]) => [name, value])[Symbol.iterator]();
+        }
+        keys() {
+            return this._list.map(([name]) => name)[Symbol.iterator]();
+        }
+        values() {
+            return this._list.map(([, value]) => value)[Symbol.iterator]();
+        }
+        [Symbol.iterator]() {
+            return this.entries();
+        }
+        toString() {
+            return native.serializeQuery(this._list);
+        }
+        _update() {
+            if (this._url) {
+                this._url._setQuery(this.toString());
+            }
+        }
+    }
+
+    class URL {
+        constructor(url, base) {
+            const parts = native.parse(String(url), baseOf(base));
+            if (parts === null) {
+                throw new TypeError(`Invalid URL: ${url}`);
+            }
+            this._parts = parts;
+            this._searchParams = new URLSearchParams(parts.search);
+            this._searchParams._url = this;
+        }
+        static canParse(url, base) {
+            return native.parse(String(url), baseOf(base)) !== null;
+        }
+        get href() {
+            return this._parts.href;
+        }
+        set href(value) {
+            this._set('href', value);
+            this._searchParams._list = native.parseQuery(this._parts.search);
+        }
+        get origin() {
+            return this._parts.origin;
+        }
+        get search() {
+            return this._parts.search;
+        }
+        set search(value) {
+            this._set('search', value);
+            this._searchParams._list = native.parseQuery(this._parts.search);
+        }
+        get searchParams() {
+            return this._searchParams;
+        }
+        toString() {
+            return this.href;
+        }
+        toJSON() {
+            return this.href;
+        }
+        _set(component, value) {
+            const parts = native.update(this._parts.href, component, String(value));
+            if (parts === null) {
+                throw new TypeError(`Invalid URL: ${value}`);
+            }
+            this._parts = parts;
+        }
+        _setQuery(query) {
+            this._set('search', query);
+        }
+    }
+
+    for (const name of ['protocol', 'username', 'password', 'host', 'hostname', 'port', 'pathname',
+        'hash']) {
+        Object.defineProperty(URL.prototype, name, {
+            get() {
+                return this._parts[name];
+            },
+            set(value) {
+                this._set(name, value);
+            },
+            configurable: true,
+        });
+    }
+
+    globalThis.URL = URL;
+    globalThis.URLSearchParams = URLSearchParams;
+}
+"#;
+
+fn parse(input: &str, base: Option<&str>) -> Option<Url> {
+    match base {
+        Some(base) => Url::parse(base).ok()?.join(input).ok(),
+        None => Url::parse(input).ok(),
+    }
+}
+
+// Sets a component the way the `URL` setters do, values that don't fit are ignored except for
+// `href`, which has to be a whole valid URL
+fn update(url: &mut Url, component: &str, value: &str) -> Option<()> {
+    match component {
+        "href" => return quirks::set_href(url, value).ok(),
+        "protocol" => quirks::set_protocol(url, value).unwrap_or_default(),
+        "username" => quirks::set_username(url, value).unwrap_or_default(),
+        "password" => quirks::set_password(url, value).unwrap_or_default(),
+        "host" => quirks::set_host(url, value).unwrap_or_default(),
+        "hostname" => quirks::set_hostname(url, value).unwrap_or_default(),
+        "port" => quirks::set_port(url, value).unwrap_or_default(),
+        "pathname" => quirks::set_pathname(url, value),
+        "search" => quirks::set_search(url, value),
+        "hash" => quirks::set_hash(url, value),
+        _ => {}
+    }
+    Some(())
+}
+
+fn parse_query(query: &str) -> Vec<Vec<String>> {
+    let query = query.strip_prefix('?').unwrap_or(query);
+    form_urlencoded::parse(query.as_bytes())
+        .map(|(name, value)| vec![name.into_owned(), value.into_owned()])
+        .collect()
+}
+
+fn serialize_query(pairs: &[Vec<String>]) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs.iter().filter_map(|pair| match pair.as_slice() {
+            [name, value] => Some((name, value)),
+            _ => None,
+        }))
+        .finish()
+}
+
+fn components(isolate: &MiniV8, url: &Url) -> mini_v8::Result<Object> {
+    let parts = isolate.create_object();
+    parts.set("href", quirks::href(url))?;
+    parts.set("origin", quirks::origin(url))?;
+    parts.set("protocol", quirks::protocol(url))?;
+    parts.set("username", quirks::username(url))?;
+    parts.set("password", quirks::password(url))?;
+    parts.set("host", quirks::host(url))?;
+    parts.set("hostname", quirks::hostname(url))?;
+    parts.set("port", quirks::port(url))?;
+    parts.set("pathname", quirks::pathname(url))?;
+    parts.set("search", quirks::search(url))?;
+    parts.set("hash", quirks::hash(url))?;
+    Ok(parts)
+}
+
+pub fn register(isolate: &MiniV8) {
+    let native = isolate.create_object();
+
+    // parse(input, base) returns the components, or null when it isn't a valid URL
+    let parse_url = isolate.create_function(|invocation| {
+        let (input, base): (String, Option<String>) = invocation.args.into(&invocation.mv8)?;
+        parse(&input, base.as_deref())
+            .map(|url| components(&invocation.mv8, &url))
+            .transpose()
+    });
+    native.set("parse", parse_url).expect("Failed to set parse");
+
+    // update(href, component, value) returns the new components, null for an invalid href
+    let update_url = isolate.create_function(|invocation| {
+        let (href, component, value): (String, String, String) =
+            invocation.args.into(&invocation.mv8)?;
+        let Ok(mut url) = Url::parse(&href) else {
+            return Ok(None);
+        };
+        if update(&mut url, &component, &value).is_none() {
+            return Ok(None);
+        }
+        components(&invocation.mv8, &url).map(Some)
+    });
+    native
+        .set("update", update_url)
+        .expect("Failed to set update");
+
+    // parseQuery(query) and serializeQuery(pairs), as application/x-www-form-urlencoded
+    let parse_query = isolate.create_function(|invocation| {
+        let (query,): (String,) = invocation.args.into(&invocation.mv8)?;
+        Ok(parse_query(&query))
+    });
+    native
+        .set("parseQuery", parse_query)
+        .expect("Failed to set parseQuery");
+    let serialize_query = isolate.create_function(|invocation| {
+        let (pairs,): (Vec<Vec<String>>,) = invocation.args.into(&invocation.mv8)?;
+        Ok(serialize_query(&pairs))
+    });
+    native
+        .set("serializeQuery", serialize_query)
+        .expect("Failed to set serializeQuery");
+
+    isolate
+        .eval::<_, Function>(PRELUDE)
+        .and_then(|prelude| prelude.call::<_, ()>((native,)))
+        .expect("Failed to set URL and URLSearchParams");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_against_a_base() {
+        let url = parse("../c?x=1", Some("https://example.com/a/b")).unwrap();
+        assert_eq!(url.as_str(), "https://example.com/c?x=1");
+        assert_eq!(quirks::origin(&url), "https://example.com");
+        assert!(parse("/relative", None).is_none());
+    }
+
+    #[test]
+    fn updates_components() {
+        let mut url = parse("https://example.com/path?q=1#top", None).unwrap();
+        update(&mut url, "port", "8080").unwrap();
+        update(&mut url, "search", "").unwrap();
+        update(&mut url, "hash", "").unwrap();
+        assert_eq!(url.as_str(), "https://example.com:8080/path");
+        // an invalid port leaves the URL alone, an invalid href doesn't
+        update(&mut url, "port", "http").unwrap();
+        assert_eq!(quirks::port(&url), "8080");
+        assert!(update(&mut url, "href", "not a url").is_none());
+    }
+
+    #[test]
+    fn round_trips_queries() {
+        let pairs = parse_query("?a=1&b=two+words&a=%F0%9F%98%80");
+        assert_eq!(
+            pairs,
+            [["a", "1"], ["b", "two words"], ["a", "😀"]]
+                .map(|pair| pair.map(String::from).to_vec())
+        );
+        assert_eq!(serialize_query(&pairs), "a=1&b=two+words&a=%F0%9F%98%80");
+    }
+}
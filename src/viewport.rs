@@ -0,0 +1,292 @@
+use std::{collections::HashMap, num::NonZeroU64, sync::Arc};
+
+use eframe::{
+    egui,
+    egui_wgpu::{self, wgpu},
+};
+
+// Orbit camera of a viewport, driven by dragging and scrolling over the element
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.0,
+            distance: 3.0,
+        }
+    }
+}
+
+impl Camera {
+    // Applies the pointer interaction of this frame, returns whether the camera moved
+    pub fn update(&mut self, ui: &egui::Ui, response: &egui::Response) -> bool {
+        let before = *self;
+        let drag = response.drag_delta();
+        self.yaw += drag.x * 0.01;
+        self.pitch = (self.pitch + drag.y * 0.01).clamp(-1.5, 1.5);
+        if response.hovered() {
+            let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+            self.distance = (self.distance * (1.0 - scroll * 0.002)).clamp(0.5, 100.0);
+        }
+        self.yaw != before.yaw || self.pitch != before.pitch || self.distance != before.distance
+    }
+}
+
+// Rust-side renderer drawing into a viewport3d element
+// Plugins create their GPU resources up front from the render state and store them in
+// the renderer's callback resources, the callback then runs inside egui's render pass
+// clipped to the element's rect
+pub trait ViewportPlugin: Send + Sync {
+    fn prepare(
+        &self,
+        _device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+        _camera: &Camera,
+        _aspect: f32,
+        _resources: &mut egui_wgpu::CallbackResources,
+    ) {
+    }
+
+    fn paint<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        resources: &'a egui_wgpu::CallbackResources,
+    );
+}
+
+// Plugins by the name used in the `renderer` prop
+#[derive(Default)]
+pub struct ViewportRegistry {
+    plugins: HashMap<String, Arc<dyn ViewportPlugin>>,
+}
+
+impl ViewportRegistry {
+    pub fn register(&mut self, name: &str, plugin: impl ViewportPlugin + 'static) {
+        self.plugins.insert(name.to_string(), Arc::new(plugin));
+    }
+
+    // Queues the plugin's drawing for this frame, `None` if no such plugin is registered
+    pub fn paint(
+        &self,
+        painter: &egui::Painter,
+        rect: egui::Rect,
+        renderer: &str,
+        camera: Camera,
+    ) -> Option<()> {
+        let plugin = self.plugins.get(renderer)?.clone();
+        painter.add(egui_wgpu::Callback::new_paint_callback(
+            rect,
+            ViewportCallback {
+                plugin,
+                camera,
+                aspect: rect.width() / rect.height().max(1.0),
+            },
+        ));
+        Some(())
+    }
+}
+
+struct ViewportCallback {
+    plugin: Arc<dyn ViewportPlugin>,
+    camera: Camera,
+    aspect: f32,
+}
+
+impl egui_wgpu::CallbackTrait for ViewportCallback {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        _screen_descriptor: &egui_wgpu::ScreenDescriptor,
+        _egui_encoder: &mut wgpu::CommandEncoder,
+        resources: &mut egui_wgpu::CallbackResources,
+    ) -> Vec<wgpu::CommandBuffer> {
+        self.plugin
+            .prepare(device, queue, &self.camera, self.aspect, resources);
+        Vec::new()
+    }
+
+    fn paint<'a>(
+        &'a self,
+        _info: egui::PaintCallbackInfo,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        resources: &'a egui_wgpu::CallbackResources,
+    ) {
+        self.plugin.paint(render_pass, resources);
+    }
+}
+
+const TRIANGLE_SHADER: &str = r#"
+struct Camera {
+    yaw: f32,
+    pitch: f32,
+    distance: f32,
+    aspect: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+struct VertexOut {
+    @location(0) color: vec4<f32>,
+    @builtin(position) position: vec4<f32>,
+};
+
+var<private> positions: array<vec3<f32>, 3> = array<vec3<f32>, 3>(
+    vec3<f32>(0.0, 1.0, 0.0),
+    vec3<f32>(-0.866, -0.5, 0.0),
+    vec3<f32>(0.866, -0.5, 0.0),
+);
+
+var<private> colors: array<vec4<f32>, 3> = array<vec4<f32>, 3>(
+    vec4<f32>(1.0, 0.0, 0.0, 1.0),
+    vec4<f32>(0.0, 1.0, 0.0, 1.0),
+    vec4<f32>(0.0, 0.0, 1.0, 1.0),
+);
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOut {
+    let p = positions[index];
+    let yawed = vec3<f32>(
+        p.x * cos(camera.yaw) + p.z * sin(camera.yaw),
+        p.y,
+        -p.x * sin(camera.yaw) + p.z * cos(camera.yaw),
+    );
+    let pitched = vec3<f32>(
+        yawed.x,
+        yawed.y * cos(camera.pitch) - yawed.z * sin(camera.pitch),
+        yawed.y * sin(camera.pitch) + yawed.z * cos(camera.pitch),
+    );
+    let depth = pitched.z + camera.distance;
+
+    var out: VertexOut;
+    out.color = colors[index];
+    out.position = vec4<f32>(pitched.x / camera.aspect, pitched.y, 0.0, depth);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) vec4<f32> {
+    return in.color;
+}
+"#;
+
+struct TriangleResources {
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+}
+
+// Built-in `triangle` renderer, a colored triangle orbited by the camera
+// The uniform buffer is shared, so all triangle viewports show the last prepared camera
+pub struct TrianglePlugin;
+
+impl TrianglePlugin {
+    pub fn new(render_state: &egui_wgpu::RenderState) -> Self {
+        let device = &render_state.device;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("viewport_triangle"),
+            source: wgpu::ShaderSource::Wgsl(TRIANGLE_SHADER.into()),
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("viewport_triangle"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: NonZeroU64::new(16),
+                },
+                count: None,
+            }],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("viewport_triangle"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("viewport_triangle"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(render_state.target_format.into())],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("viewport_triangle"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("viewport_triangle"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        render_state
+            .renderer
+            .write()
+            .callback_resources
+            .insert(TriangleResources {
+                pipeline,
+                bind_group,
+                uniform_buffer,
+            });
+        Self
+    }
+}
+
+impl ViewportPlugin for TrianglePlugin {
+    fn prepare(
+        &self,
+        _device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        camera: &Camera,
+        aspect: f32,
+        resources: &mut egui_wgpu::CallbackResources,
+    ) {
+        let Some(resources) = resources.get::<TriangleResources>() else {
+            return;
+        };
+        let uniforms: Vec<u8> = [camera.yaw, camera.pitch, camera.distance, aspect]
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect();
+        queue.write_buffer(&resources.uniform_buffer, 0, &uniforms);
+    }
+
+    fn paint<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        resources: &'a egui_wgpu::CallbackResources,
+    ) {
+        let Some(resources) = resources.get::<TriangleResources>() else {
+            return;
+        };
+        render_pass.set_pipeline(&resources.pipeline);
+        render_pass.set_bind_group(0, &resources.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use eframe::egui;
+use mini_v8::{Error as MiniV8Error, Function, MiniV8, Object, Value};
+
+use crate::event::{call_handler, EventBuilder};
+
+const EVENT_TYPES: &[&str] = &["resize", "closeRequested", "focus"];
+
+#[derive(Default)]
+struct WindowState {
+    handlers: HashMap<String, Vec<Function>>,
+    size: Option<egui::Vec2>,
+    focused: Option<bool>,
+}
+
+// The `vgui.window` object, handlers are registered from scripts and called from the update loop
+pub struct WindowEvents {
+    object: Object,
+    state: Rc<RefCell<WindowState>>,
+}
+
+impl WindowEvents {
+    pub fn new(isolate: &MiniV8) -> Self {
+        let object = isolate.create_object();
+        let state = Rc::new(RefCell::new(WindowState::default()));
+
+        // on('resize' | 'closeRequested' | 'focus', handler)
+        let state_clone = state.clone();
+        let on = isolate.create_function(move |invocation| {
+            let (event_type, handler): (String, Function) =
+                invocation.args.into(&invocation.mv8)?;
+            if !EVENT_TYPES.contains(&event_type.as_str()) {
+                return Err(MiniV8Error::ExternalError(
+                    format!("Unknown window event: {}", event_type).into(),
+                ));
+            }
+            state_clone
+                .borrow_mut()
+                .handlers
+                .entry(event_type)
+                .or_default()
+                .push(handler);
+            Ok(())
+        });
+        object.set("on", on).expect("Failed to set window.on");
+
+        Self { object, state }
+    }
+
+    pub fn object(&self) -> Object {
+        self.object.clone()
+    }
+
+    // Calls the handlers of an event, returns whether one of them vetoed it by calling
+    // `event.preventDefault()` or returning `false`
+    fn emit(
+        &self,
+        isolate: &MiniV8,
+        ctx: &egui::Context,
+        event_type: &str,
+        fields: impl FnOnce(EventBuilder) -> EventBuilder,
+    ) -> bool {
+        let handlers = self
+            .state
+            .borrow()
+            .handlers
+            .get(event_type)
+            .cloned()
+            .unwrap_or_default();
+        if handlers.is_empty() {
+            return false;
+        }
+
+        let prevented = Rc::new(Cell::new(false));
+        let prevented_clone = prevented.clone();
+        let prevent_default = isolate.create_function(move |_| {
+            prevented_clone.set(true);
+            Ok(())
+        });
+        // window events target the root element
+        let event = fields(EventBuilder::new(isolate, event_type, 0))
+            .input(ctx)
+            .field("preventDefault", prevent_default)
+            .build();
+        for handler in handlers {
+            let result = call_handler(isolate, &handler, event_type, vec![event.clone()]);
+            if matches!(result, Some(Value::Boolean(false))) {
+                prevented.set(true);
+            }
+        }
+        prevented.get()
+    }
+
+    pub fn update(&self, isolate: &MiniV8, ctx: &egui::Context) {
+        let (size, focused, close_requested) = ctx.input(|i| {
+            (
+                i.screen_rect().size(),
+                i.viewport().focused,
+                i.viewport().close_requested(),
+            )
+        });
+
+        let previous_size = self.state.borrow_mut().size.replace(size);
+        if previous_size.is_some_and(|previous_size| previous_size != size) {
+            self.emit(isolate, ctx, "resize", |event| {
+                event.field("width", size.x).field("height", size.y)
+            });
+        }
+
+        if let Some(focused) = focused {
+            let previous_focused = self.state.borrow_mut().focused.replace(focused);
+            if previous_focused != Some(focused) {
+                self.emit(isolate, ctx, "focus", |event| {
+                    event.field("focused", focused)
+                });
+            }
+        }
+
+        if close_requested && self.emit(isolate, ctx, "closeRequested", |event| event) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        }
+    }
+}